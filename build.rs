@@ -1,9 +1,270 @@
+use std::collections::BTreeSet;
 use std::env;
+use std::fmt::Write as _;
 use std::fs;
 use std::path::PathBuf;
 
+const LEVELS_PATH: &str = "levels.txt";
+
+// keep in sync with src/game.rs, checked again in src/levels.rs
+const NUM_ROWS: u32 = 21;
+const BOARD_WIDTH: usize = 6;
+/// Rows at the top that need to stay free for new pieces
+const SPAWN_ROWS: usize = 2;
+
+const PIECES: &[&str] = &["O", "I", "J", "L", "T", "S", "Z"];
+
+#[derive(Default)]
+struct Level {
+    obstacles: Vec<u32>,
+    tough: Vec<u32>,
+    board: Vec<[bool; BOARD_WIDTH]>,
+    narrator: Vec<String>,
+    pieces: Vec<&'static str>,
+    allowed: Vec<&'static str>,
+    /// Delay and pixels
+    gravity: Option<[u8; 2]>,
+    /// After, every and max, `Some(None)` turns the ramp off
+    ramp: Option<Option<[u8; 3]>>,
+}
+
+impl Level {
+    fn add_rows(rows: &mut Vec<u32>, taken: &mut BTreeSet<u32>, args: &str) -> Result<(), String> {
+        if args.is_empty() {
+            return Err("Expected at least one row".to_string());
+        }
+        for row in args.split_whitespace() {
+            let row = row
+                .parse::<u32>()
+                .map_err(|_| format!("Invalid row number: {row:?}"))?;
+            if !(1..=NUM_ROWS).contains(&row) {
+                return Err(format!("Row {row} is out of range 1..={NUM_ROWS}"));
+            }
+            if !taken.insert(row) {
+                return Err(format!("Row {row} already has an obstacle"));
+            }
+            rows.push(row);
+        }
+        Ok(())
+    }
+
+    fn add_board_row(&mut self, line: &str) -> Result<(), String> {
+        let mut row = [false; BOARD_WIDTH];
+        if line.chars().count() != BOARD_WIDTH {
+            return Err(format!("Board rows need to be {BOARD_WIDTH} tiles wide"));
+        }
+        for (tile, c) in row.iter_mut().zip(line.chars()) {
+            *tile = match c {
+                'o' => true,
+                '.' => false,
+                _ => return Err(format!("Invalid board tile: {c:?}")),
+            };
+        }
+        if row.iter().all(|tile| *tile) {
+            return Err("Board row is complete and would be cleared right away".to_string());
+        }
+        if self.board.len() >= NUM_ROWS as usize - SPAWN_ROWS {
+            return Err("Board is too high, there's no room for new pieces".to_string());
+        }
+        self.board.push(row);
+        Ok(())
+    }
+
+    fn write(&self, out: &mut String) {
+        writeln!(out, "    Level {{").unwrap();
+        writeln!(out, "        obstacles: &{:?},", self.obstacles).unwrap();
+        writeln!(out, "        tough: &{:?},", self.tough).unwrap();
+        writeln!(out, "        board: &{:?},", self.board).unwrap();
+        writeln!(out, "        narrator: &{:?},", self.narrator).unwrap();
+        write!(out, "        pieces: &[").unwrap();
+        for piece in &self.pieces {
+            write!(out, "Piece::{piece}, ").unwrap();
+        }
+        writeln!(out, "],").unwrap();
+        write!(out, "        allowed: &[").unwrap();
+        for piece in &self.allowed {
+            write!(out, "Piece::{piece}, ").unwrap();
+        }
+        writeln!(out, "],").unwrap();
+        match self.gravity {
+            Some([delay, pixels]) => writeln!(
+                out,
+                "        gravity: Some(Gravity::new({delay}, {pixels})),"
+            ),
+            None => writeln!(out, "        gravity: None,"),
+        }
+        .unwrap();
+        match self.ramp {
+            Some(Some([after, every, max])) => writeln!(
+                out,
+                "        ramp: Some(Ramp {{ after: {after}, every: {every}, max: {max} }}),"
+            ),
+            Some(None) => writeln!(out, "        ramp: None,"),
+            None => writeln!(out, "        ramp: gravity::RAMP,"),
+        }
+        .unwrap();
+        writeln!(out, "    }},").unwrap();
+    }
+}
+
+fn parse_pieces(pieces: &mut Vec<&'static str>, args: &str) -> Result<(), String> {
+    if args.is_empty() {
+        return Err("Expected at least one piece".to_string());
+    }
+    args.split_whitespace().try_for_each(|piece| {
+        let piece = PIECES
+            .iter()
+            .find(|known| **known == piece)
+            .ok_or_else(|| format!("Unknown piece: {piece:?}"))?;
+        pieces.push(piece);
+        Ok(())
+    })
+}
+
+/// Exactly `N` numbers between 0 and 255
+fn parse_numbers<const N: usize>(args: &str) -> Result<[u8; N], String> {
+    let mut numbers = [0; N];
+    let mut args = args.split_whitespace();
+    for number in numbers.iter_mut() {
+        let arg = args.next().ok_or_else(|| format!("Expected {N} numbers"))?;
+        *number = arg
+            .parse()
+            .map_err(|_| format!("Invalid number: {arg:?}"))?;
+    }
+    if args.next().is_some() {
+        return Err(format!("Expected {N} numbers"));
+    }
+    Ok(numbers)
+}
+
+fn parse_gravity(args: &str) -> Result<[u8; 2], String> {
+    let [delay, pixels] = parse_numbers(args)?;
+    if delay == 0 || pixels == 0 {
+        return Err("Gravity needs a delay and pixels of at least 1".to_string());
+    }
+    Ok([delay, pixels])
+}
+
+fn parse_ramp(args: &str) -> Result<Option<[u8; 3]>, String> {
+    if args == "off" {
+        return Ok(None);
+    }
+    let [after, every, max] = parse_numbers(args)?;
+    if every == 0 {
+        return Err("Ramp needs to increase every 1 tick or more".to_string());
+    }
+    Ok(Some([after, every, max]))
+}
+
+#[derive(Default)]
+struct Levels {
+    campaign: Vec<Level>,
+    endless: Vec<Level>,
+}
+
+fn parse(text: &str) -> Result<Levels, (usize, String)> {
+    let mut levels = Levels::default();
+    let mut current: Option<&mut Level> = None;
+    let mut taken = BTreeSet::new();
+    let mut in_board = false;
+
+    for (idx, line) in text.lines().enumerate() {
+        let num = idx + 1;
+        let line = line.split('#').next().unwrap_or_default().trim_end();
+        if line.trim().is_empty() {
+            in_board = false;
+            continue;
+        }
+        let (key, args) = line.split_once(' ').unwrap_or((line, ""));
+        let args = args.trim();
+
+        match key {
+            "level" | "loop" => {
+                let list = if key == "level" {
+                    &mut levels.campaign
+                } else {
+                    &mut levels.endless
+                };
+                list.push(Level::default());
+                current = list.last_mut();
+                taken.clear();
+                in_board = false;
+                continue;
+            }
+            _ => (),
+        }
+
+        let Some(level) = current.as_mut() else {
+            return Err((num, "Expected `level` or `loop` first".to_string()));
+        };
+
+        if in_board && !line.contains(' ') && line.chars().all(|c| c == 'o' || c == '.') {
+            level.add_board_row(line).map_err(|err| (num, err))?;
+            continue;
+        }
+        in_board = false;
+
+        let result = match key {
+            "obstacle" => Level::add_rows(&mut level.obstacles, &mut taken, args),
+            "tough" => Level::add_rows(&mut level.tough, &mut taken, args),
+            "narrator" => {
+                // an empty line still takes a moment to scroll through
+                level.narrator.push(if args.is_empty() {
+                    " ".to_string()
+                } else {
+                    args.to_string()
+                });
+                Ok(())
+            }
+            "pieces" => parse_pieces(&mut level.pieces, args),
+            "allow" if level.allowed.is_empty() => parse_pieces(&mut level.allowed, args),
+            "allow" => Err("Level already has allowed pieces".to_string()),
+            "gravity" if level.gravity.is_none() => {
+                parse_gravity(args).map(|gravity| level.gravity = Some(gravity))
+            }
+            "gravity" => Err("Level already has a gravity".to_string()),
+            "ramp" if level.ramp.is_none() => parse_ramp(args).map(|ramp| level.ramp = Some(ramp)),
+            "ramp" => Err("Level already has a ramp".to_string()),
+            "board" if level.board.is_empty() => {
+                in_board = true;
+                Ok(())
+            }
+            "board" => Err("Level already has a board".to_string()),
+            _ => Err(format!("Unknown entry: {key:?}")),
+        };
+        result.map_err(|err| (num, err))?;
+    }
+
+    if levels.campaign.is_empty() || levels.endless.is_empty() {
+        return Err((
+            0,
+            "Need at least one `level` and one `loop` level".to_string(),
+        ));
+    }
+
+    Ok(levels)
+}
+
 fn main() {
-    let out = &PathBuf::from(env::var_os("OUT_DIR").unwrap());
-    fs::copy("memory.x", out.join("memory.x")).unwrap();
-    println!("cargo:rerun-if-changed=memory.x");
+    println!("cargo:rerun-if-changed={LEVELS_PATH}");
+
+    let text = fs::read_to_string(LEVELS_PATH).unwrap();
+    let levels = parse(&text).unwrap_or_else(|(line, err)| panic!("{LEVELS_PATH}:{line}: {err}"));
+
+    let mut out = String::new();
+    writeln!(out, "pub(super) const NUM_ROWS: u32 = {NUM_ROWS};").unwrap();
+    writeln!(out, "pub(super) const BOARD_WIDTH: usize = {BOARD_WIDTH};").unwrap();
+    writeln!(out, "pub(super) const CAMPAIGN: &[Level] = &[").unwrap();
+    for level in &levels.campaign {
+        level.write(&mut out);
+    }
+    writeln!(out, "];").unwrap();
+    writeln!(out, "pub(super) const ENDLESS: &[Level] = &[").unwrap();
+    for level in &levels.endless {
+        level.write(&mut out);
+    }
+    writeln!(out, "];").unwrap();
+
+    let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
+    fs::write(out_dir.join("levels.rs"), out).unwrap();
 }
@@ -1,6 +1,8 @@
 MEMORY {
     BOOT2 : ORIGIN = 0x10000000, LENGTH = 0x100
-    FLASH : ORIGIN = 0x10000100, LENGTH = 2048K - 0x100
+    FLASH : ORIGIN = 0x10000100, LENGTH = 2048K - 0x100 - 16K
+    /* 4 sectors for persistent storage, see src/storage.rs in the game crate */
+    STORAGE : ORIGIN = 0x10000000 + 2048K - 16K, LENGTH = 16K
     RAM   : ORIGIN = 0x20000000, LENGTH = 256K
 }
 
@@ -0,0 +1,26 @@
+use eh0::blocking::i2c;
+use game_chop_chop::settings::{Orientation, Settings};
+use sh1106::{Builder, interface::DisplayInterface, prelude::*};
+
+pub fn init<T: i2c::Write>(i2c: T, settings: &Settings) -> GraphicsMode<I2cInterface<T>> {
+    let mut display: GraphicsMode<_> = Builder::new()
+        .with_rotation(rotation(settings.orientation))
+        .connect_i2c(i2c)
+        .into();
+    display.init().ok();
+    display.set_contrast(settings.display_contrast()).ok();
+    display
+}
+
+/// Apply settings that changed while running
+pub fn apply<DI: DisplayInterface>(display: &mut GraphicsMode<DI>, settings: &Settings) {
+    display.set_rotation(rotation(settings.orientation)).ok();
+    display.set_contrast(settings.display_contrast()).ok();
+}
+
+fn rotation(orientation: Orientation) -> DisplayRotation {
+    match orientation {
+        Orientation::Normal => DisplayRotation::Rotate270,
+        Orientation::Flipped => DisplayRotation::Rotate90,
+    }
+}
@@ -0,0 +1,39 @@
+use core::convert::Infallible;
+use game_chop_chop::storage::{self, Page};
+use rp2040_flash::flash;
+
+/// Size of the flash chip on the rp2040-zero
+const FLASH_SIZE: u32 = 2048 * 1024;
+/// Start of the `STORAGE` region in memory.x, relative to the start of flash
+const STORAGE_OFFSET: u32 = FLASH_SIZE - storage::CAPACITY;
+/// Flash is memory mapped here, reads go through the cache
+const XIP_BASE: u32 = 0x1000_0000;
+
+/// The sectors reserved for storage at the end of flash
+pub struct Storage;
+
+impl storage::Flash for Storage {
+    type Error = Infallible;
+
+    fn read(&mut self, offset: u32, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let src = (XIP_BASE + STORAGE_OFFSET + offset) as *const u8;
+        // SAFETY: the storage region is mapped and nothing else in the program refers to it
+        unsafe { core::ptr::copy_nonoverlapping(src, buf.as_mut_ptr(), buf.len()) };
+        Ok(())
+    }
+
+    fn erase(&mut self, offset: u32) -> Result<(), Self::Error> {
+        // code runs from flash, nothing may fetch from it while it's written
+        cortex_m::interrupt::free(|_| unsafe {
+            flash::flash_range_erase(STORAGE_OFFSET + offset, storage::SECTOR_SIZE, true);
+        });
+        Ok(())
+    }
+
+    fn program(&mut self, offset: u32, page: &Page) -> Result<(), Self::Error> {
+        cortex_m::interrupt::free(|_| unsafe {
+            flash::flash_range_program(STORAGE_OFFSET + offset, page, true);
+        });
+        Ok(())
+    }
+}
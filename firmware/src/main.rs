@@ -0,0 +1,147 @@
+#![no_std]
+#![no_main]
+
+mod display;
+mod flash;
+
+use defmt_rtt as _;
+use eh0::timer::CountDown;
+use embedded_hal::digital::InputPin;
+use fugit::ExtU32;
+use fugit::RateExtU32;
+use game_chop_chop::ctx::Context;
+use game_chop_chop::gfx;
+use game_chop_chop::highscores::HighScores;
+use game_chop_chop::input::{Button, Input};
+use game_chop_chop::random::{self, HealthError, Random};
+use game_chop_chop::settings::Settings;
+use game_chop_chop::storage::Store;
+use panic_halt as _;
+use waveshare_rp2040_zero::entry;
+use waveshare_rp2040_zero::{
+    Pins, XOSC_CRYSTAL_FREQ,
+    hal::{
+        Sio,
+        clocks::{Clock, init_clocks_and_plls},
+        i2c::I2C,
+        pac,
+        rosc::RingOscillator,
+        timer::Timer,
+        watchdog::Watchdog,
+    },
+};
+
+#[entry]
+fn main() -> ! {
+    let mut pac = pac::Peripherals::take().unwrap();
+
+    // Configure clocks and timers
+    let mut watchdog = Watchdog::new(pac.WATCHDOG);
+    let clocks = init_clocks_and_plls(
+        XOSC_CRYSTAL_FREQ,
+        pac.XOSC,
+        pac.CLOCKS,
+        pac.PLL_SYS,
+        pac.PLL_USB,
+        &mut pac.RESETS,
+        &mut watchdog,
+    )
+    .ok()
+    .unwrap();
+
+    let timer = Timer::new(pac.TIMER, &mut pac.RESETS, &clocks);
+    let mut delay = timer.count_down();
+    let mut rosc = RingOscillator::new(pac.ROSC).initialize();
+
+    // Configure gpio
+    let sio = Sio::new(pac.SIO);
+    let pins = Pins::new(
+        pac.IO_BANK0,
+        pac.PADS_BANK0,
+        sio.gpio_bank0,
+        &mut pac.RESETS,
+    );
+
+    // Configure display
+    let i2c = I2C::i2c1(
+        pac.I2C1,
+        pins.gp26.into_pull_type().into_function(), // sda
+        pins.gp27.into_pull_type().into_function(), // scl
+        400.kHz(),
+        &mut pac.RESETS,
+        clocks.peripheral_clock.freq(),
+    );
+
+    // load persistent data
+    let mut store = Store::open(flash::Storage).unwrap();
+    let highscores = HighScores::load(&store);
+    let settings = Settings::load(&store);
+
+    let mut display = display::init(i2c, &settings);
+
+    // keep playing without good entropy, but tell the player why pieces may repeat
+    if let Err(err) = random::health_check(&mut rosc) {
+        let reason = match err {
+            HealthError::Repetition => "is stuck",
+            HealthError::Bias(_) => "is biased",
+        };
+        display.clear();
+        gfx::message(
+            &mut display,
+            &["Random source", reason, "", "Pieces may", "repeat"],
+        );
+        display.flush().unwrap();
+        delay.start(3.secs());
+        let _ = nb::block!(delay.wait());
+    }
+
+    // configure button
+    let mut button_down_pin = pins.gp0.into_pull_up_input();
+    let mut button_right_pin = pins.gp1.into_pull_up_input();
+    let mut button_up_pin = pins.gp3.into_pull_up_input();
+    let mut button_left_pin = pins.gp7.into_pull_up_input();
+    let mut button_center_pin = pins.gp8.into_pull_up_input();
+
+    let mut input = Input::new();
+
+    let mut ctx = Context::new()
+        .with_highscores(highscores)
+        .with_settings(settings);
+    let mut random = Random::new(rosc);
+
+    // enter loop
+    loop {
+        // a flipped display swaps the buttons, so up is still up
+        let orientation = ctx.settings().orientation;
+        input.update(ctx.ticks(), |button| match orientation.remap(button) {
+            Button::Down => button_down_pin.is_low().unwrap(),
+            Button::Right => button_right_pin.is_low().unwrap(),
+            Button::Up => button_up_pin.is_low().unwrap(),
+            Button::Left => button_left_pin.is_low().unwrap(),
+            Button::Center => button_center_pin.is_low().unwrap(),
+        });
+        ctx.consume(input.queue());
+
+        ctx.tick(&mut random);
+
+        // persist new high scores
+        if let Some(highscores) = ctx.take_unsaved_highscores() {
+            highscores.save(&mut store).unwrap();
+        }
+
+        // persist and apply new settings
+        if let Some(settings) = ctx.take_unsaved_settings() {
+            settings.save(&mut store).unwrap();
+            display::apply(&mut display, &settings);
+        }
+
+        // render screen
+        display.clear();
+        ctx.render(&mut display);
+        display.flush().unwrap();
+
+        // sleep for frame rate
+        delay.start((ctx.settings().frame_delay as u32).millis());
+        let _ = nb::block!(delay.wait());
+    }
+}
@@ -0,0 +1,20 @@
+#![no_main]
+
+use game_chop_chop::invariants::{self, Step};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let Some((level, data)) = data.split_first() else {
+        return;
+    };
+    let Some((seed, steps)) = data.split_first_chunk::<8>() else {
+        return;
+    };
+
+    let level = u32::from(*level % 16);
+    let seed = u64::from_le_bytes(*seed);
+    let steps = steps.iter().copied().map(Step::from_byte);
+    if let Err(violation) = invariants::run(level, seed, steps) {
+        panic!("Invariant violated: {violation:?}");
+    }
+});
@@ -0,0 +1,52 @@
+use clap::Parser;
+use game_chop_chop::input::DropStyle;
+use std::path::PathBuf;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Run the game headless and write frames as png")]
+pub struct Args {
+    /// Increase logging output (can be used multiple times)
+    #[arg(short, long, global = true, action(clap::ArgAction::Count))]
+    pub verbose: u8,
+    /// Directory to write the png files to
+    pub output: PathBuf,
+    /// Number of ticks to simulate, defaults to the length of the replay or 1000
+    #[arg(short = 'n', long)]
+    pub ticks: Option<u32>,
+    /// Write every nth frame
+    #[arg(long, default_value = "1")]
+    pub every: u32,
+    /// Write these specific frames (can be used multiple times)
+    #[arg(long = "frame")]
+    pub frames: Vec<u32>,
+    /// Seed for the piece sequence and random input
+    #[arg(long, default_value = "0")]
+    pub seed: u64,
+    /// Play back a recorded session instead of generating random input
+    #[arg(long, conflicts_with = "bot")]
+    pub replay: Option<PathBuf>,
+    /// Let the computer play instead of generating random input
+    #[arg(long)]
+    pub bot: bool,
+    /// Buttons that drop pieces (modern or classic), replays bring their own
+    #[arg(long, default_value = "modern", value_parser = parse_drop_style)]
+    pub drop: DropStyle,
+    /// Scale each pixel up to a square of this size
+    #[arg(long, default_value = "4")]
+    pub scale: u32,
+}
+
+fn parse_drop_style(s: &str) -> Result<DropStyle, String> {
+    s.parse()
+        .map_err(|_| format!("Unknown drop style: {s:?}, expected modern or classic"))
+}
+
+impl Args {
+    pub fn should_write(&self, tick: u32) -> bool {
+        if !self.frames.is_empty() {
+            self.frames.contains(&tick)
+        } else {
+            tick.is_multiple_of(self.every.max(1))
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use game_chop_chop::bot;
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::input::{Button, ButtonEvent, Kind};
+use rand::Rng;
+use rand::rngs::StdRng;
+use std::collections::VecDeque;
+
+/// Chance per tick that random input presses a button
+const RANDOM_PRESS_PROBABILITY: f64 = 0.2;
+
+pub enum Input {
+    Replay(VecDeque<ButtonEvent>),
+    Random(Box<StdRng>),
+    Bot,
+}
+
+impl Input {
+    /// Return all button events of the given tick
+    pub fn poll(&mut self, tick: u32, ctx: &Context) -> Vec<ButtonEvent> {
+        let tapped = match self {
+            Input::Replay(events) => {
+                let mut polled = Vec::new();
+                while events.front().is_some_and(|event| event.tick <= tick) {
+                    polled.push(events.pop_front().unwrap());
+                }
+                return polled;
+            }
+            Input::Random(rng) => {
+                if rng.gen_bool(RANDOM_PRESS_PROBABILITY) {
+                    Some(Button::ALL[rng.gen_range(0..Button::ALL.len())])
+                } else {
+                    None
+                }
+            }
+            Input::Bot => match ctx.screen() {
+                Screen::Game(game) => bot::next_button(game),
+                // start a new game
+                _ => Some(Button::Center),
+            },
+        };
+        // buttons are released right away
+        tapped
+            .into_iter()
+            .flat_map(|button| {
+                [Kind::Pressed, Kind::Released].map(|kind| ButtonEvent::new(button, kind, tick))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,113 @@
+mod args;
+mod input;
+
+use crate::args::Args;
+use crate::input::Input;
+use anyhow::{Context as _, Result, anyhow};
+use clap::Parser;
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use env_logger::Env;
+use game_chop_chop::ctx::Context;
+use game_chop_chop::game::SwitchTo;
+use game_chop_chop::gfx::{self, Framebuffer};
+use game_chop_chop::random::Random;
+use game_chop_chop::replay::Replay;
+use log::{debug, info};
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::fs::{self, File};
+use std::io::BufWriter;
+use std::path::Path;
+
+const DEFAULT_TICKS: u32 = 1000;
+
+fn write_png(path: &Path, frame: &Framebuffer, scale: u32) -> Result<()> {
+    let scale = scale.max(1);
+    let width = gfx::UDISPLAY_WIDTH * scale;
+    let height = gfx::UDISPLAY_HEIGHT * scale;
+
+    let mut data = Vec::with_capacity((width * height) as usize);
+    for y in 0..height {
+        for x in 0..width {
+            let point = Point::new((x / scale) as i32, (y / scale) as i32);
+            data.push(if gfx::pixel(frame, point) { 0xff } else { 0x00 });
+        }
+    }
+
+    let file = File::create(path).with_context(|| format!("Failed to create file: {path:?}"))?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width, height);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(&data)?;
+    writer.finish()?;
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+    let log_level = match args.verbose {
+        0 => "info",
+        1 => "info,chop_sim=debug",
+        _ => "debug",
+    };
+    env_logger::init_from_env(Env::default().default_filter_or(log_level));
+
+    let (seed, drop_style, mut input, ticks) = if let Some(path) = &args.replay {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read replay file: {path:?}"))?;
+        let replay = Replay::parse(&text)
+            .map_err(|err| anyhow!("{err}"))
+            .with_context(|| format!("Failed to parse replay file: {path:?}"))?;
+        let events = replay.events().collect();
+        let drop_style = replay.drop_style();
+        (
+            replay.seed(),
+            drop_style,
+            Input::Replay(events),
+            replay.end(),
+        )
+    } else if args.bot {
+        (args.seed, args.drop, Input::Bot, DEFAULT_TICKS)
+    } else {
+        let rng = StdRng::seed_from_u64(args.seed);
+        let input = Input::Random(Box::new(rng));
+        (args.seed, args.drop, input, DEFAULT_TICKS)
+    };
+    let ticks = args.ticks.unwrap_or(ticks);
+
+    fs::create_dir_all(&args.output)
+        .with_context(|| format!("Failed to create output directory: {:?}", args.output))?;
+
+    let mut ctx = Context::new().with_drop_style(drop_style);
+    let mut random = Random::seed_from_u64(seed);
+    let mut frame = Framebuffer::new();
+
+    for tick in 0..ticks {
+        for event in input.poll(tick, &ctx) {
+            debug!("Button {:?} {:?} in tick {tick}", event.button, event.kind);
+            ctx.handle(event);
+        }
+
+        match ctx.tick(&mut random) {
+            Some(SwitchTo::NextLevel(level)) => info!("Reached level {level} in tick {tick}"),
+            Some(SwitchTo::GameOver(level)) => info!("Game over in level {level} in tick {tick}"),
+            None => (),
+        }
+
+        if !args.should_write(tick) {
+            continue;
+        }
+
+        frame.clear(BinaryColor::Off)?;
+        ctx.render(&mut frame);
+
+        let path = args.output.join(format!("frame-{tick:05}.png"));
+        debug!("Writing frame to {path:?}");
+        write_png(&path, &frame, args.scale)?;
+    }
+
+    info!("Simulated {ticks} ticks");
+    Ok(())
+}
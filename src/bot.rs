@@ -0,0 +1,165 @@
+//! Computer player that plays a `Game` through the same buttons as a human.
+//!
+//! The bot is stateless, every time it's asked it looks at all placements the
+//! current piece can still reach, picks the best resulting board and returns
+//! the next button press towards it.
+
+use crate::game::{self, Game, Lanes};
+use crate::gfx::tile::Tile;
+use crate::input::Button;
+
+const PLAYFIELD: core::ops::Range<usize> = game::MIN_LANE as usize..game::NUM_LANES as usize;
+const NUM_ROWS: usize = game::NUM_ROWS as usize;
+
+/// Clearing the row the blade is stuck on is the whole point
+const FREED_BLADE: i32 = 10_000;
+/// Tiles in the row the blade is stuck on
+const BLADE_ROW_TILE: i32 = 40;
+/// Any other completed row
+const CLEARED_ROW: i32 = 10;
+/// Gaps that are covered by tiles, at or above the blade row
+const HOLE: i32 = -30;
+/// Tiles stacked above the blade row, they get in the way
+const TILE_ABOVE_BLADE: i32 = -25;
+/// Difference in height between neighboring lanes
+const BUMPINESS: i32 = -2;
+/// Height of the highest tile, to keep room for new pieces
+const HEIGHT: i32 = -4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Placement {
+    /// Number of rotations, starting from the current orientation
+    pub rotations: u8,
+    /// The lane of the piece grid's left edge
+    pub lane: u32,
+    pub score: i32,
+}
+
+impl Placement {
+    /// Next button press to get the piece into this placement
+    pub fn next_button(&self, game: &Game) -> Button {
+        if self.rotations > 0 {
+            Button::Up
+        } else if self.lane < game.lane() {
+            Button::Left
+        } else if self.lane > game.lane() {
+            Button::Right
+        } else {
+            game.drop_style().hard_drop()
+        }
+    }
+}
+
+/// Press and release a button in the game, without going through `Context`
+pub fn press(game: &mut Game, button: Button) {
+    match button {
+        Button::Up => game.button_up(),
+        Button::Down => game.button_down(),
+        Button::Left => game.button_left(),
+        Button::Right => game.button_right(),
+        Button::Center => game.button_center(),
+    }
+    game.button_released(button);
+}
+
+/// Find the best reachable placement of the current piece
+pub fn best_placement(game: &Game) -> Option<Placement> {
+    let blade_row = game.next_obstacle().0;
+    let mut best: Option<Placement> = None;
+
+    let mut rotated = game.clone();
+    for rotations in 0..4 {
+        if rotations > 0 {
+            let before = *rotated.piece().tiles();
+            rotated.button_up();
+            // rotation is blocked, or the piece is symmetric
+            if *rotated.piece().tiles() == before {
+                continue;
+            }
+        }
+
+        for direction in [Button::Left, Button::Right] {
+            let mut moved = rotated.clone();
+            loop {
+                if let Some(lanes) = moved.drop_preview() {
+                    let score = score(lanes, blade_row);
+                    if best.is_none_or(|best| score > best.score) {
+                        best = Some(Placement {
+                            rotations,
+                            lane: moved.lane(),
+                            score,
+                        });
+                    }
+                }
+
+                let lane = moved.lane();
+                press(&mut moved, direction);
+                if moved.lane() == lane {
+                    break;
+                }
+            }
+        }
+    }
+
+    best
+}
+
+/// Decide the next button press, `None` if there's nothing to do right now
+pub fn next_button(game: &Game) -> Option<Button> {
+    if game.in_transition() {
+        None
+    } else if game.narrating() {
+        Some(Button::Down)
+    } else {
+        best_placement(game).map(|placement| placement.next_button(game))
+    }
+}
+
+fn score(mut lanes: Lanes, blade_row: Option<usize>) -> i32 {
+    let mut score = 0;
+
+    // clear completed rows, the same way the game does
+    for y in 0..NUM_ROWS {
+        if !PLAYFIELD.clone().all(|x| lanes[x][y].is_some()) {
+            continue;
+        }
+        score += if Some(y) == blade_row {
+            FREED_BLADE
+        } else {
+            CLEARED_ROW
+        };
+        for x in PLAYFIELD {
+            for y in (0..y).rev() {
+                lanes[x][y + 1] = lanes[x][y];
+            }
+            lanes[x][0] = None;
+        }
+    }
+
+    // rows up to and including the blade row are the ones that matter
+    let relevant = blade_row.unwrap_or(NUM_ROWS - 1);
+
+    let mut previous_height = None;
+    for x in PLAYFIELD {
+        let lane = &lanes[x];
+        let top = lane.iter().position(Option::is_some).unwrap_or(NUM_ROWS);
+        let height = (NUM_ROWS - top) as i32;
+
+        score += HEIGHT * height;
+        if let Some(previous) = previous_height {
+            score += BUMPINESS * i32::abs_diff(height, previous) as i32;
+        }
+        previous_height = Some(height);
+
+        for (y, tile) in lane.iter().enumerate().take(relevant + 1).skip(top) {
+            match tile {
+                Some(Tile { .. }) if y < relevant => score += TILE_ABOVE_BLADE,
+                Some(Tile { .. }) if y == relevant => score += BLADE_ROW_TILE,
+                Some(_) => (),
+                None => score += HOLE,
+            }
+        }
+    }
+
+    score
+}
@@ -1,158 +1,443 @@
+use crate::demo::Demo;
 use crate::game::{Game, SwitchTo};
 use crate::gameover::{Decision, Gameover};
+use crate::generator::{Generated, Search};
+use crate::highscores::{HighScores, Table};
+use crate::initials::Initials;
+use crate::input::{Button, ButtonEvent, DropStyle, EventQueue, Kind};
 use crate::intro::Intro;
+use crate::levels::Level;
+use crate::paused::{Choice, Paused};
 use crate::random::Random;
+use crate::score::Score;
+use crate::settings::{Menu, Settings};
 use core::fmt::Debug;
 use embedded_graphics::{draw_target::DrawTarget, pixelcolor::BinaryColor};
 use rand_core::RngCore;
 
 #[allow(clippy::large_enum_variant)]
-pub enum Context {
+pub enum Screen {
     Intro(Intro),
+    Demo(Demo),
     Game(Game),
+    Paused(Paused),
     Gameover(Gameover),
+    Initials(Initials),
+    HighScores(Table),
+    Settings(Menu),
+}
+
+pub struct Context {
+    screen: Screen,
+    highscores: HighScores,
+    /// The table changed since it was last saved
+    unsaved: bool,
+    settings: Settings,
+    /// The settings changed since they were last saved
+    unsaved_settings: bool,
+    /// Ticks since the start, button events are stamped with it
+    ticks: u32,
+    /// Lanes the piece moved since the first of left and right went down.
+    /// The left+right chord that pauses comes after both presses, it takes them back.
+    steered: i32,
+    /// Number of left and right buttons that are down
+    steering: u8,
+    /// Generated level that's played next, searched a step every tick
+    search: Option<Search>,
+    /// Result of the search for the level that's played, to restart it
+    generated: Option<(u32, Option<Generated>)>,
+    /// Level that starts once its search is done, with the score so far
+    waiting: Option<(u32, Score)>,
+}
+
+impl Default for Context {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Context {
     pub const fn new() -> Self {
-        // Context::Gameover(Gameover::new(1337))
-        Context::Intro(Intro::new())
+        Context {
+            // Screen::Gameover(Gameover::new(1337))
+            screen: Screen::Intro(Intro::new()),
+            highscores: HighScores::new(),
+            unsaved: false,
+            settings: Settings::new(),
+            unsaved_settings: false,
+            ticks: 0,
+            steered: 0,
+            steering: 0,
+            search: None,
+            generated: None,
+            waiting: None,
+        }
     }
 
-    fn start_game(&mut self, level: u32) {
-        let mut game = Game::new(level);
-        // TODO: refactor this
-        match level {
-            0 => {
-                game.add_obstacle_at_row(7);
-            }
-            1 => {
-                game.add_obstacle_at_row(2);
-            }
-            2 => {
-                game.add_obstacle_at_row(13);
-                game.add_obstacle_at_row(7);
-            }
-            3 => {
-                // this is a good maximum height
-                game.add_obstacle_at_row(15);
-                game.add_obstacle_at_row(4);
-                game.add_obstacle_at_row(1);
-            }
-            4 => {
-                // demo the tough obstacle
-                game.add_obstacle_at_row(4);
-                game.add_tough_obstacle_at_row(7);
-            }
-            5 => {
-                // now have the player actually solve it
-                game.add_tough_obstacle_at_row(4);
-                game.add_obstacle_at_row(7);
+    /// Start with the high scores that were saved before
+    pub const fn with_highscores(mut self, highscores: HighScores) -> Self {
+        self.highscores = highscores;
+        self
+    }
+
+    /// Start with the settings that were saved before
+    pub const fn with_settings(mut self, settings: Settings) -> Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Buttons used to drop pieces in every game started from now on
+    pub const fn with_drop_style(mut self, drop_style: DropStyle) -> Self {
+        self.settings.drop_style = drop_style;
+        self
+    }
+
+    /// Skip the intro and start right away at the given level
+    pub fn at_level(level: u32) -> Self {
+        let mut ctx = Self::new();
+        ctx.start_game(level);
+        ctx
+    }
+
+    #[inline]
+    pub fn screen(&self) -> &Screen {
+        &self.screen
+    }
+
+    #[inline]
+    pub fn highscores(&self) -> &HighScores {
+        &self.highscores
+    }
+
+    /// Returns the table once after it changed, so it can be saved
+    pub fn take_unsaved_highscores(&mut self) -> Option<HighScores> {
+        core::mem::take(&mut self.unsaved).then_some(self.highscores)
+    }
+
+    #[inline]
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    /// Returns the settings once after they changed, so they can be saved and applied
+    pub fn take_unsaved_settings(&mut self) -> Option<Settings> {
+        core::mem::take(&mut self.unsaved_settings).then_some(self.settings)
+    }
+
+    /// Start a level with the current settings. A generated level waits
+    /// for its search, the screen doesn't change until then.
+    fn enter_level(&mut self, level: u32, score: Score) {
+        if level >= Level::CAMPAIGN && self.generated.is_none_or(|(played, _)| played != level) {
+            let search = match &mut self.search {
+                Some(search) if search.level() == level => search,
+                search => search.insert(Search::new(level)),
+            };
+            if !search.done() {
+                self.waiting = Some((level, score));
+                return;
             }
-            num => match num % 4 {
-                0 => {
-                    game.add_obstacle_at_row(3);
-                    game.add_obstacle_at_row(4);
-                    game.add_obstacle_at_row(5);
-                    game.add_obstacle_at_row(6);
-                }
-                1 => {
-                    game.add_tough_obstacle_at_row(4);
-                    game.add_obstacle_at_row(5);
-                }
-                2 => {
-                    game.add_obstacle_at_row(2);
-                    game.add_obstacle_at_row(6);
-                    game.add_obstacle_at_row(14);
-                }
-                _ => {
-                    game.add_obstacle_at_row(1);
-                    game.add_obstacle_at_row(5);
-                    game.add_tough_obstacle_at_row(13);
-                }
-            },
+            self.generated = Some((level, search.found()));
+        }
+        self.waiting = None;
+
+        let game = match self.generated {
+            Some((played, Some(generated))) if played == level => generated.into_game(level),
+            _ => Game::from_level(level),
+        };
+        let game = game
+            .with_score(score)
+            .with_drop_style(self.settings.drop_style)
+            .with_up_rotation(self.settings.up_rotation);
+        self.screen = Screen::Game(game);
+        self.steered = 0;
+
+        // the next generated level is ready by the time this one is won
+        let next = level + 1;
+        if next >= Level::CAMPAIGN
+            && self
+                .search
+                .as_ref()
+                .is_none_or(|search| search.level() != next)
+        {
+            self.search = Some(Search::new(next));
+        }
+    }
+
+    fn start_game(&mut self, level: u32) {
+        self.enter_level(level, Score::new());
+    }
+
+    fn button_up(&mut self) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.button_up(),
+            Screen::Demo(demo) => demo.button_pressed(),
+            Screen::Game(game) => game.button_up(),
+            Screen::Paused(paused) => paused.button_up(),
+            Screen::Gameover(gameover) => gameover.button_up(),
+            Screen::Initials(initials) => initials.button_up(),
+            Screen::HighScores(table) => table.button_pressed(),
+            Screen::Settings(menu) => menu.button_up(),
+        }
+    }
+
+    fn button_down(&mut self) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.button_down(),
+            Screen::Demo(demo) => demo.button_pressed(),
+            Screen::Game(game) => game.button_down(),
+            Screen::Paused(paused) => paused.button_down(),
+            Screen::Gameover(gameover) => gameover.button_down(),
+            Screen::Initials(initials) => initials.button_down(),
+            Screen::HighScores(table) => table.button_pressed(),
+            Screen::Settings(menu) => menu.button_down(),
         }
-        *self = Self::Game(game);
     }
 
-    pub fn button_up(&mut self) {
-        match self {
-            Self::Intro(_intro) => (),
-            Self::Game(game) => game.button_up(),
-            Self::Gameover(gameover) => gameover.button_up(),
+    fn button_right(&mut self) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.button_right(),
+            Screen::Demo(demo) => demo.button_pressed(),
+            Screen::Game(game) => game.button_right(),
+            Screen::Paused(paused) => paused.button_right(),
+            Screen::Gameover(gameover) => gameover.button_right(),
+            Screen::Initials(initials) => initials.button_right(),
+            Screen::HighScores(table) => table.button_pressed(),
+            Screen::Settings(menu) => menu.button_right(),
         }
     }
 
-    pub fn button_down(&mut self) {
-        match self {
-            Self::Intro(intro) => intro.button_down(),
-            Self::Game(game) => game.button_down(),
-            Self::Gameover(gameover) => gameover.button_down(),
+    fn button_left(&mut self) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.button_left(),
+            Screen::Demo(demo) => demo.button_pressed(),
+            Screen::Game(game) => game.button_left(),
+            Screen::Paused(_paused) => (),
+            Screen::Gameover(_gameover) => (),
+            Screen::Initials(initials) => initials.button_left(),
+            Screen::HighScores(table) => table.button_pressed(),
+            Screen::Settings(menu) => menu.button_left(),
         }
     }
 
-    pub fn button_right(&mut self) {
-        match self {
-            Self::Intro(intro) => intro.button_right(),
-            Self::Game(game) => game.button_right(),
-            Self::Gameover(gameover) => gameover.button_right(),
+    fn button_center(&mut self) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.button_center(),
+            Screen::Demo(demo) => demo.button_pressed(),
+            Screen::Game(game) => game.button_center(),
+            Screen::Paused(paused) => paused.button_center(),
+            Screen::Gameover(gameover) => gameover.button_center(),
+            Screen::Initials(initials) => initials.button_center(),
+            Screen::HighScores(table) => table.button_pressed(),
+            Screen::Settings(menu) => menu.button_center(),
         }
     }
 
-    pub fn button_left(&mut self) {
-        match self {
-            Self::Intro(_intro) => (),
-            Self::Game(game) => game.button_left(),
-            Self::Gameover(_gameover) => (),
+    #[inline]
+    pub const fn ticks(&self) -> u32 {
+        self.ticks
+    }
+
+    pub fn handle(&mut self, event: ButtonEvent) {
+        let lane = match &self.screen {
+            Screen::Game(game) => Some(game.lane()),
+            _ => None,
+        };
+        match (event.kind, event.button) {
+            (Kind::Pressed | Kind::Repeated, Button::Up) => self.button_up(),
+            (Kind::Pressed | Kind::Repeated, Button::Down) => self.button_down(),
+            (Kind::Pressed | Kind::Repeated, Button::Left) => self.button_left(),
+            (Kind::Pressed | Kind::Repeated, Button::Right) => self.button_right(),
+            (Kind::Pressed | Kind::Repeated, Button::Center) => self.button_center(),
+            (Kind::Released, button) => self.button_released(button),
+            (Kind::LongPress, button) => self.long_press(button),
+            (Kind::DoubleTap, button) => self.double_tap(button),
+            (Kind::Chord(Button::Right), Button::Left) => self.toggle_pause(),
+            // not used by any screen yet
+            (Kind::Chord(_), _) => (),
+        }
+        self.track_steering(event, lane);
+    }
+
+    /// Handle all queued events, oldest first
+    pub fn consume(&mut self, queue: &mut EventQueue) {
+        while let Some(event) = queue.pop() {
+            self.handle(event);
+        }
+    }
+
+    /// Only the game cares about buttons being released
+    fn button_released(&mut self, button: Button) {
+        if let Screen::Game(game) = &mut self.screen {
+            game.button_released(button);
+        }
+    }
+
+    fn long_press(&mut self, button: Button) {
+        if let Screen::Game(game) = &mut self.screen {
+            game.long_press(button);
         }
     }
 
-    pub fn button_center(&mut self) {
-        match self {
-            Self::Intro(intro) => intro.button_center(),
-            Self::Game(game) => game.button_center(),
-            Self::Gameover(gameover) => gameover.button_center(),
+    fn double_tap(&mut self, button: Button) {
+        match &mut self.screen {
+            Screen::Intro(intro) => intro.double_tap(button),
+            Screen::Game(game) => game.double_tap(button),
+            _ => (),
         }
     }
 
-    pub fn tick<R: RngCore>(&mut self, random: &mut Random<R>) {
-        match self {
-            Self::Intro(intro) => {
+    /// Pause the game or resume it, the paused game doesn't change at all
+    fn toggle_pause(&mut self) {
+        self.screen = match core::mem::replace(&mut self.screen, Screen::Intro(Intro::new())) {
+            Screen::Game(mut game) => {
+                // undo the presses that are part of the chord
+                game.steer_back(core::mem::take(&mut self.steered));
+                Screen::Paused(Paused::new(game))
+            }
+            Screen::Paused(paused) => Screen::Game(paused.into_game()),
+            screen => screen,
+        };
+    }
+
+    /// Count the lanes the presses of left and right moved the piece from `lane`
+    fn track_steering(&mut self, event: ButtonEvent, lane: Option<u32>) {
+        if !matches!(event.button, Button::Left | Button::Right) {
+            return;
+        }
+        match event.kind {
+            Kind::Pressed => {
+                if self.steering == 0 {
+                    self.steered = 0;
+                }
+                self.steering = (self.steering + 1).min(2);
+                if let (Some(lane), Screen::Game(game)) = (lane, &self.screen) {
+                    self.steered += game.lane() as i32 - lane as i32;
+                }
+            }
+            Kind::Released => self.steering = self.steering.saturating_sub(1),
+            _ => (),
+        }
+    }
+
+    /// Advance by one frame, returns the level transition if one happened
+    pub fn tick<R: RngCore>(&mut self, random: &mut Random<R>) -> Option<SwitchTo> {
+        self.ticks = self.ticks.wrapping_add(1);
+        if let Some(search) = &mut self.search {
+            search.step();
+        }
+        // keep showing the screen until the generated level is found
+        if let Some((level, score)) = self.waiting {
+            self.enter_level(level, score);
+            return None;
+        }
+
+        match &mut self.screen {
+            Screen::Intro(intro) => {
                 if intro.start {
                     self.start_game(0);
+                } else if intro.highscores {
+                    self.screen = Screen::HighScores(Table::new(self.highscores));
+                } else if intro.settings {
+                    self.screen = Screen::Settings(Menu::new(self.settings));
+                } else {
+                    intro.tick();
+                    if intro.idle() {
+                        self.screen = Screen::Demo(Demo::new());
+                    }
                 }
+                None
             }
-            Self::Game(game) => {
+            Screen::Demo(demo) => {
+                demo.tick(random);
+                if demo.done() {
+                    self.screen = Screen::Intro(Intro::new());
+                }
+                None
+            }
+            Screen::Game(game) => {
                 game.tick(random);
                 // check for game over/next level
-                match game.transition() {
+                let transition = game.transition();
+                match transition {
                     Some(SwitchTo::NextLevel(level)) => {
-                        self.start_game(level);
+                        let score = *game.score();
+                        self.enter_level(level, score);
                     }
                     Some(SwitchTo::GameOver(level)) => {
-                        *self = Self::Gameover(Gameover::new(level));
+                        let score = *game.score();
+                        self.screen = if self.highscores.qualifies(score.total()) {
+                            Screen::Initials(Initials::new(level, score))
+                        } else {
+                            Screen::Gameover(Gameover::new(level, score))
+                        };
                     }
                     None => (),
                 }
+                transition
             }
-            Self::Gameover(gameover) => match gameover.decision() {
-                Some(Decision::Quit) => {
-                    *self = Self::Intro(Intro::new());
+            Screen::Paused(paused) => {
+                match paused.choice() {
+                    Some(Choice::Resume) => self.toggle_pause(),
+                    Some(Choice::Restart) => {
+                        let level = paused.game().level();
+                        self.start_game(level);
+                    }
+                    Some(Choice::Quit) => self.screen = Screen::Intro(Intro::new()),
+                    None => (),
                 }
-                Some(Decision::Restart) => self.start_game(0),
-                None => (),
-            },
-        };
+                None
+            }
+            Screen::Gameover(gameover) => {
+                match gameover.decision() {
+                    Some(Decision::Quit) => {
+                        self.screen = Screen::Intro(Intro::new());
+                    }
+                    Some(Decision::Restart) => self.start_game(0),
+                    None => (),
+                }
+                None
+            }
+            Screen::Initials(initials) => {
+                if let Some(entry) = initials.entry() {
+                    let gameover = Gameover::new(initials.level(), *initials.score());
+                    self.highscores.insert(entry);
+                    self.unsaved = true;
+                    self.screen = Screen::Gameover(gameover);
+                }
+                None
+            }
+            Screen::HighScores(table) => {
+                if table.done() {
+                    self.screen = Screen::Intro(Intro::new());
+                }
+                None
+            }
+            Screen::Settings(menu) => {
+                if menu.done() {
+                    if *menu.settings() != self.settings {
+                        self.settings = *menu.settings();
+                        self.unsaved_settings = true;
+                    }
+                    self.screen = Screen::Intro(Intro::new());
+                }
+                None
+            }
+        }
     }
 
     pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
     where
         <D as DrawTarget>::Error: Debug,
     {
-        match self {
-            Self::Intro(intro) => intro.render(display),
-            Self::Game(game) => game.render(display),
-            Self::Gameover(gameover) => gameover.render(display),
+        match &self.screen {
+            Screen::Intro(intro) => intro.render(display),
+            Screen::Demo(demo) => demo.render(display),
+            Screen::Game(game) => game.render(display),
+            Screen::Paused(paused) => paused.render(display),
+            Screen::Gameover(gameover) => gameover.render(display),
+            Screen::Initials(initials) => initials.render(display),
+            Screen::HighScores(table) => table.render(display),
+            Screen::Settings(menu) => menu.render(display),
         }
     }
 }
@@ -0,0 +1,136 @@
+//! Self-playing game shown after the intro has been idle for a while.
+
+use crate::bot;
+use crate::game::{Game, SwitchTo};
+use crate::generator::Search;
+use crate::gfx;
+use crate::levels::Level;
+use crate::random::Random;
+use crate::timer::Timer;
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+use rand_core::RngCore;
+
+/// Ticks between button presses of the bot, so it's possible to follow along
+const PRESS_DELAY: u8 = 3;
+/// Ticks the overlay is shown, and then hidden
+const BLINK_DELAY: u8 = 15;
+
+const OVERLAY_Y_OFFSET: i32 = 50;
+const OVERLAY_TEXT: &[&str] = &["press any", "button"];
+const LINE_HEIGHT: i32 = 8;
+const BACKGROUND_PADDING: i32 = 2;
+
+pub struct Demo {
+    game: Game,
+    press: Timer,
+    blink: Timer,
+    visible: bool,
+    exit: bool,
+    /// Generated level after the current one, searched a step every tick
+    search: Option<Search>,
+}
+
+impl Default for Demo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Demo {
+    pub fn new() -> Self {
+        Demo {
+            game: Game::from_level(0),
+            press: Timer::new(PRESS_DELAY),
+            blink: Timer::new(BLINK_DELAY),
+            visible: true,
+            exit: false,
+            search: None,
+        }
+    }
+
+    #[inline]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Any button ends the demo
+    #[inline(always)]
+    pub fn button_pressed(&mut self) {
+        self.exit = true;
+    }
+
+    /// Returns true if a button was pressed or the bot lost
+    #[inline]
+    pub fn done(&self) -> bool {
+        self.exit || matches!(self.game.transition(), Some(SwitchTo::GameOver(_)))
+    }
+
+    pub fn tick<R: RngCore>(&mut self, random: &mut Random<R>) {
+        if self.press.step()
+            && let Some(button) = bot::next_button(&self.game)
+        {
+            bot::press(&mut self.game, button);
+        }
+
+        if self.blink.step() {
+            self.visible = !self.visible;
+        }
+
+        self.game.tick(random);
+        let level = self.game.level();
+        if level + 1 >= Level::CAMPAIGN {
+            let search = self.search.get_or_insert_with(|| Search::new(level + 1));
+            search.step();
+        }
+
+        // keep showing the transition until the next level is generated
+        if let Some(SwitchTo::NextLevel(level)) = self.game.transition()
+            && self.search.as_ref().is_none_or(|search| search.done())
+        {
+            let game = match self.search.take() {
+                Some(search) => search.game(),
+                None => Game::from_level(level),
+            };
+            self.game = game.with_score(*self.game.score());
+        }
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        self.game.render(display);
+
+        if !self.visible {
+            return;
+        }
+
+        let style = gfx::TEXT_STYLE;
+        let height = OVERLAY_TEXT.len() as i32 * LINE_HEIGHT;
+        Rectangle::new(
+            Point::new(0, OVERLAY_Y_OFFSET - BACKGROUND_PADDING),
+            Size::new(
+                gfx::UDISPLAY_WIDTH,
+                (height + 2 * BACKGROUND_PADDING) as u32,
+            ),
+        )
+        .into_styled(gfx::BLACK)
+        .draw(display)
+        .unwrap();
+
+        for (num, text) in OVERLAY_TEXT.iter().enumerate() {
+            let y = OVERLAY_Y_OFFSET + num as i32 * LINE_HEIGHT;
+            let x = gfx::text_align_center(text, gfx::DISPLAY_WIDTH, style.font);
+            Text::with_baseline(text, Point::new(x, y), style, Baseline::Top)
+                .draw(display)
+                .unwrap();
+        }
+    }
+}
@@ -1,11 +1,17 @@
 use crate::gfx;
 use crate::gfx::blade::Blade;
+use crate::gfx::hud::Hud;
 use crate::gfx::tile::Tile;
+use crate::gravity::{self, Gravity, LockDelay, Ramp};
+use crate::input::{Button, DropStyle};
+use crate::levels::Level;
 use crate::narrator::Narrator;
-use crate::pieces::{self, Piece};
+use crate::pieces::{self, Direction, Piece};
 use crate::random::Random;
+use crate::randomizer::{self, PieceSet, Randomizer};
+use crate::score::Score;
 use crate::timer::Timer;
-use core::fmt::Debug;
+use core::fmt::{self, Debug};
 use embedded_graphics::{
     draw_target::DrawTarget,
     pixelcolor::BinaryColor,
@@ -15,17 +21,18 @@ use embedded_graphics::{
 };
 use rand_core::RngCore;
 
-const MIN_LANE: u32 = 2;
+pub const MIN_LANE: u32 = 2;
 pub const NUM_LANES: u32 = 8;
-const NUM_ROWS: u32 = gfx::UDISPLAY_HEIGHT / LANE_WIDTH;
+pub const NUM_ROWS: u32 = gfx::UDISPLAY_HEIGHT / LANE_WIDTH;
 pub const LANE_WIDTH: u32 = 6;
-const LANE_OFFSET: Point = Point::new(
+pub const LANE_OFFSET: Point = Point::new(
     gfx::DISPLAY_WIDTH - (LANE_WIDTH * NUM_LANES) as i32 - RIGHT_BORDER,
     0,
 );
 pub const RIGHT_BORDER: i32 = 1;
 
-const DROP_SPEED: u8 = 1;
+/// Pixels per step while down is held
+const SOFT_DROP_SPEED: i32 = LANE_WIDTH as i32 / 2;
 const INITIAL_DROP_POSITION: i32 = -(4 * LANE_WIDTH as i32);
 const INITIAL_LANE: u32 = MIN_LANE + 2;
 
@@ -35,12 +42,25 @@ const GAME_OVER_DELAY: u8 = 3;
 static_assertions::const_assert_eq!(NUM_ROWS, 21);
 static_assertions::const_assert!(INITIAL_LANE + 4 <= NUM_LANES);
 
-#[derive(Clone, Copy)]
+/// tiles[x][y] of the whole board, including the blade lanes
+pub type Lanes = [[Option<Tile>; NUM_ROWS as usize]; NUM_LANES as usize];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SwitchTo {
     NextLevel(u32),
     GameOver(u32),
 }
 
+/// A turn of the piece and the kick it took
+#[derive(Debug, Clone, Copy)]
+struct Turn {
+    direction: Direction,
+    lanes: i32,
+    rows: i32,
+    /// It started the lock delay over
+    reset_lock: bool,
+}
+
 #[derive(Clone)]
 pub struct Game {
     level: u32,
@@ -50,27 +70,68 @@ pub struct Game {
     drop: i32,
     drop_timer: Timer,
     drop_speed: i32,
+    gravity: Gravity,
+    ramp: Option<Ramp>,
+    /// Ticks played while the blade is stuck, not counting the narrator
+    ramp_timer: Timer,
+    lock_delay: Option<LockDelay>,
+    /// The piece landed and is placed once this is due
+    lock: Option<Timer>,
+    /// Times the lock delay was started over for the current piece
+    lock_resets: u8,
     narrator: Option<Narrator>,
-    lanes: [[Option<Tile>; NUM_ROWS as usize]; NUM_LANES as usize],
+    randomizer: Randomizer,
+    pieces_placed: u32,
+    score: Score,
+    /// Row the blade is stuck on and for how long
+    stuck: Option<(usize, Timer)>,
+    /// Where the piece was when it was hard dropped
+    hard_drop: Option<i32>,
+    held: Option<Piece>,
+    /// The hold slot was already used for the current piece
+    held_this_drop: bool,
+    drop_style: DropStyle,
+    /// Which way up turns the piece
+    up_rotation: Direction,
+    /// What the last two presses of up did, the latest first, `None` if it was blocked
+    up_turns: [Option<Turn>; 2],
+    /// Down is held
+    soft_drop: bool,
+    /// Center is held, releasing it drops the piece unless it was a long-press
+    center: bool,
+    lanes: Lanes,
     transiton: Option<(SwitchTo, Timer)>,
 }
 
 impl Game {
     pub const fn new(level: u32) -> Self {
-        let narrator = match level {
-            0 => Some(Narrator::level0()),
-            _ => None,
-        };
-
         Game {
             level,
             blade: Blade::new(),
             lane: INITIAL_LANE,
             piece: Piece::T.into_grid(),
             drop: INITIAL_DROP_POSITION,
-            drop_timer: Timer::new(DROP_SPEED),
-            drop_speed: 1,
-            narrator,
+            drop_timer: Timer::new(Gravity::of_level(level).delay),
+            drop_speed: Gravity::of_level(level).pixels as i32,
+            gravity: Gravity::of_level(level),
+            ramp: gravity::RAMP,
+            ramp_timer: Timer::infinite(),
+            lock_delay: Some(LockDelay::DEFAULT),
+            lock: None,
+            lock_resets: 0,
+            narrator: None,
+            randomizer: Randomizer::new(),
+            pieces_placed: 0,
+            score: Score::new(),
+            stuck: None,
+            hard_drop: None,
+            held: None,
+            held_this_drop: false,
+            drop_style: DropStyle::Modern,
+            up_rotation: Direction::Clockwise,
+            up_turns: [None; 2],
+            soft_drop: false,
+            center: false,
             lanes: [
                 [None; NUM_ROWS as usize],
                 [Some(Tile { wall: true }); NUM_ROWS as usize],
@@ -93,6 +154,136 @@ impl Game {
         }
     }
 
+    /// Set up the board as described in `levels.txt`, after the campaign
+    /// that's one of the `loop` levels. Generated levels come from `generator::Search`.
+    pub fn from_level(level: u32) -> Self {
+        let definition = Level::get(level);
+        let mut game = Game::new(level);
+
+        for row in definition.obstacles {
+            game.add_obstacle_at_row(*row);
+        }
+        for row in definition.tough {
+            game.add_tough_obstacle_at_row(*row);
+        }
+
+        let offset = NUM_ROWS as usize - definition.board.len();
+        for (y, row) in definition.board.iter().enumerate() {
+            for (x, tile) in row.iter().enumerate() {
+                if *tile {
+                    game.lanes[MIN_LANE as usize + x][offset + y] = Some(Tile { wall: false });
+                }
+            }
+        }
+
+        if !definition.narrator.is_empty() {
+            game.narrator = Some(Narrator::new(definition.narrator));
+        }
+
+        let mut sequence = definition.pieces;
+        if let [first, rest @ ..] = sequence {
+            game.piece = first.into_grid();
+            sequence = rest;
+        }
+        game.randomizer = game
+            .randomizer
+            .with_allowed(PieceSet::from_slice(definition.allowed))
+            .with_sequence(sequence);
+
+        if let Some(gravity) = definition.gravity {
+            game = game.with_gravity(gravity);
+        }
+        game.with_ramp(definition.ramp)
+    }
+
+    /// Keep the points from previous levels
+    pub const fn with_score(mut self, score: Score) -> Self {
+        self.score = score;
+        self
+    }
+
+    /// Start with this piece instead of the first one of the level
+    pub fn with_piece(mut self, piece: Piece) -> Self {
+        self.spawn(piece);
+        self
+    }
+
+    /// Fall at a different speed than the level would
+    pub const fn with_gravity(mut self, gravity: Gravity) -> Self {
+        self.gravity = gravity;
+        self.drop_timer = Timer::new(gravity.delay);
+        self.drop_speed = gravity.pixels as i32;
+        self
+    }
+
+    /// Speed up while the blade is stuck, `None` turns it off
+    pub const fn with_ramp(mut self, ramp: Option<Ramp>) -> Self {
+        self.ramp = ramp;
+        self
+    }
+
+    /// Wait before placing a landed piece, `None` places it right away
+    pub const fn with_lock_delay(mut self, lock_delay: Option<LockDelay>) -> Self {
+        self.lock_delay = lock_delay;
+        self
+    }
+
+    /// Returns true while a landed piece waits to be placed
+    #[inline]
+    pub fn locking(&self) -> bool {
+        self.lock.is_some()
+    }
+
+    /// Current gravity, including the ramp
+    pub fn gravity(&self) -> Gravity {
+        Gravity::new(
+            self.gravity.delay,
+            self.gravity.pixels.saturating_add(self.ramped()),
+        )
+    }
+
+    /// Extra pixels per step, the longer the blade is stuck
+    fn ramped(&self) -> u8 {
+        match (self.ramp, self.stuck) {
+            (Some(ramp), Some(_)) => ramp.extra(self.ramp_timer.get()),
+            _ => 0,
+        }
+    }
+
+    /// Deal the pieces from this seed, they don't depend on the random passed to `tick`
+    pub fn with_piece_seed(mut self, seed: u64) -> Self {
+        self.randomizer = self.randomizer.with_seed(seed);
+        self
+    }
+
+    /// How the random pieces are picked
+    pub const fn with_mode(mut self, mode: randomizer::Mode) -> Self {
+        self.randomizer = self.randomizer.with_mode(mode);
+        self
+    }
+
+    pub const fn with_drop_style(mut self, drop_style: DropStyle) -> Self {
+        self.drop_style = drop_style;
+        self
+    }
+
+    #[inline]
+    pub fn drop_style(&self) -> DropStyle {
+        self.drop_style
+    }
+
+    /// Which way up turns the piece
+    pub const fn with_up_rotation(mut self, direction: Direction) -> Self {
+        self.up_rotation = direction;
+        self
+    }
+
+    /// Number of upcoming pieces that are shown
+    pub const fn with_preview(mut self, preview: usize) -> Self {
+        self.randomizer = self.randomizer.with_preview(preview);
+        self
+    }
+
     fn try_to<F: Fn(&mut Self)>(&mut self, update: F) -> bool {
         let mut next = self.clone();
         update(&mut next);
@@ -116,30 +307,21 @@ impl Game {
         }
 
         // check tiles
-        let offset_y = (self.drop / LANE_WIDTH as i32) + 1;
-        for (x, lane) in self.piece.tiles().iter().enumerate() {
-            let x = self.lane as usize + x;
-            for (y, tile) in lane.iter().enumerate() {
-                // if there's nothing in the piece grid
-                if !tile {
-                    continue;
-                }
-
-                // only consider piece tiles that are visible
-                let Ok(y) = usize::try_from(offset_y + y as i32) else {
-                    continue;
-                };
+        for (x, y) in self.piece_tiles() {
+            // only consider piece tiles that are visible
+            let Ok(y) = usize::try_from(y) else {
+                continue;
+            };
 
-                // check for collision
-                let Some(lane) = self.lanes.get(x) else {
-                    return true;
-                };
-                let Some(tile) = lane.get(y) else {
-                    return true;
-                };
-                if tile.is_some() {
-                    return true;
-                }
+            // check for collision
+            let Some(lane) = self.lanes.get(x as usize) else {
+                return true;
+            };
+            let Some(tile) = lane.get(y) else {
+                return true;
+            };
+            if tile.is_some() {
+                return true;
             }
         }
 
@@ -147,40 +329,265 @@ impl Game {
         false
     }
 
+    /// Position of the current piece's tiles as (lane, row), rows above the screen are negative
+    pub fn piece_tiles(&self) -> impl Iterator<Item = (u32, i32)> + '_ {
+        let offset_y = (self.drop / LANE_WIDTH as i32) + 1;
+        self.piece
+            .tiles()
+            .iter()
+            .enumerate()
+            .flat_map(move |(x, lane)| {
+                lane.iter()
+                    .enumerate()
+                    // if there's nothing in the piece grid
+                    .filter(|(_, tile)| **tile)
+                    .map(move |(y, _)| (self.lane + x as u32, offset_y + y as i32))
+            })
+    }
+
+    #[inline]
+    pub fn lanes(&self) -> &Lanes {
+        &self.lanes
+    }
+
+    #[inline]
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    /// The lane of the piece grid's left edge
+    #[inline]
+    pub fn lane(&self) -> u32 {
+        self.lane
+    }
+
+    #[inline]
+    pub fn piece(&self) -> &pieces::Grid {
+        &self.piece
+    }
+
+    /// Upcoming pieces, next one first
+    #[inline]
+    pub fn preview(&self) -> impl Iterator<Item = Piece> + '_ {
+        self.randomizer.preview()
+    }
+
+    /// Number of pieces that landed on the board in this level
+    #[inline]
+    pub fn pieces_placed(&self) -> u32 {
+        self.pieces_placed
+    }
+
+    /// Points collected so far, including previous levels
+    #[inline]
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    /// Returns true while the narrator is shown, button presses go to the narrator
+    #[inline]
+    pub fn narrating(&self) -> bool {
+        self.narrator.is_some()
+    }
+
+    /// The board as it would be after dropping the current piece straight down,
+    /// before completed rows are cleared. Returns `None` if the piece doesn't fit.
+    pub fn drop_preview(&self) -> Option<Lanes> {
+        let mut game = self.clone();
+        if game.collides() {
+            return None;
+        }
+        // collisions are checked per row, no need to move pixel by pixel
+        loop {
+            game.drop = game.drop.saturating_add(LANE_WIDTH as i32);
+            if game.collides() {
+                game.drop -= LANE_WIDTH as i32;
+                break;
+            }
+        }
+        game.persist_piece().then_some(game.lanes)
+    }
+
     pub fn button_up(&mut self) {
-        self.try_to(|game| {
-            game.piece.rotate();
+        let turn = self.turn(self.up_rotation);
+        self.up_turns = [turn, self.up_turns[0]];
+    }
+
+    /// Turn the piece, moving it to the first free spot of its kick table
+    pub fn rotate(&mut self, direction: Direction) -> bool {
+        self.turn(direction).is_some()
+    }
+
+    fn turn(&mut self, direction: Direction) -> Option<Turn> {
+        for (lanes, rows) in self.piece.kicks(direction) {
+            let Some(lane) = self.lane.checked_add_signed(*lanes) else {
+                continue;
+            };
+            let drop = self.drop + rows * LANE_WIDTH as i32;
+            if self.try_to(|game| {
+                game.piece.rotate(direction);
+                game.lane = lane;
+                game.drop = drop;
+            }) {
+                let resets = self.lock_resets;
+                self.reset_lock();
+                return Some(Turn {
+                    direction,
+                    lanes: *lanes,
+                    rows: *rows,
+                    reset_lock: self.lock_resets > resets,
+                });
+            }
+        }
+        None
+    }
+
+    /// Take a turn back, from wherever the piece moved since, if it still fits
+    fn undo(&mut self, turn: Turn) -> bool {
+        let Some(lane) = self.lane.checked_add_signed(-turn.lanes) else {
+            return false;
+        };
+        let drop = self.drop - turn.rows * LANE_WIDTH as i32;
+        let undone = self.try_to(|game| {
+            game.piece.rotate(turn.direction.opposite());
+            game.lane = lane;
+            game.drop = drop;
         });
+        if undone && turn.reset_lock {
+            self.lock_resets -= 1;
+        }
+        undone
     }
 
     pub fn button_down(&mut self) {
         if let Some(narrator) = self.narrator.take() {
             self.narrator = narrator.button_pressed();
-        } else {
-            self.try_to(|game| {
-                game.drop_speed = i32::MAX;
-            });
+            return;
+        }
+        match self.drop_style {
+            DropStyle::Modern => self.soft_drop = true,
+            DropStyle::Classic => self.hard_drop(),
+        }
+    }
+
+    /// Drop the piece all the way down
+    pub fn hard_drop(&mut self) {
+        if self.try_to(|game| {
+            game.drop_speed = i32::MAX;
+        }) {
+            self.hard_drop.get_or_insert(self.drop);
         }
     }
 
     pub fn button_right(&mut self) {
-        self.try_to(|game| {
+        if self.try_to(|game| {
             game.lane = game.lane.saturating_add(1);
-        });
+        }) {
+            self.reset_lock();
+        }
     }
 
     pub fn button_left(&mut self) {
-        self.try_to(|game| {
+        if self.try_to(|game| {
             game.lane = game.lane.saturating_sub(1);
-        });
+        }) {
+            self.reset_lock();
+        }
+    }
+
+    /// Move the piece back by the lanes it was steered, if it still fits there
+    pub fn steer_back(&mut self, lanes: i32) {
+        let Some(lane) = self.lane.checked_add_signed(-lanes) else {
+            return;
+        };
+        self.try_to(|game| game.lane = lane);
+    }
+
+    /// Start the lock delay over after the landed piece moved, if there are resets left
+    fn reset_lock(&mut self) {
+        let Some(lock_delay) = self.lock_delay else {
+            return;
+        };
+        if let Some(timer) = &mut self.lock
+            && self.lock_resets < lock_delay.resets
+        {
+            timer.reset();
+            self.lock_resets += 1;
+        }
+    }
+
+    /// Returns true if the piece can't move further down
+    fn resting(&self) -> bool {
+        let mut next = self.clone();
+        next.drop = next.drop.saturating_add(1);
+        next.collides()
     }
 
-    #[inline(always)]
     pub fn button_center(&mut self) {
-        self.button_down();
+        if let Some(narrator) = self.narrator.take() {
+            self.narrator = narrator.button_pressed();
+            return;
+        }
+        match self.drop_style {
+            DropStyle::Modern => self.center = true,
+            DropStyle::Classic => self.hold(),
+        }
+    }
+
+    pub fn button_released(&mut self, button: Button) {
+        match button {
+            Button::Down => self.soft_drop = false,
+            // it was a short press, otherwise the piece was put on hold already
+            Button::Center if core::mem::take(&mut self.center) => self.hard_drop(),
+            _ => (),
+        }
+    }
+
+    /// A double-tap of up turns the piece the other way. Both taps turned it
+    /// already, they're taken back before it turns once.
+    pub fn double_tap(&mut self, button: Button) {
+        if button != Button::Up {
+            return;
+        }
+        for turn in core::mem::take(&mut self.up_turns).into_iter().flatten() {
+            if !self.undo(turn) {
+                return;
+            }
+        }
+        self.rotate(self.up_rotation.opposite());
+    }
+
+    pub fn long_press(&mut self, button: Button) {
+        if button == Button::Center && core::mem::take(&mut self.center) {
+            self.hold();
+        }
+    }
+
+    /// Swap the current piece with the held one, or the next one if nothing
+    /// is held yet. This works once per piece and only if the swapped piece fits.
+    pub fn hold(&mut self) {
+        if self.held_this_drop || self.in_transition() {
+            return;
+        }
+        self.try_to(|game| {
+            let Some(piece) = game.held.or_else(|| game.randomizer.take()) else {
+                return;
+            };
+            game.held = Some(game.piece.piece);
+            game.spawn(piece);
+            game.held_this_drop = true;
+        });
+    }
+
+    /// The piece in the hold slot
+    #[inline]
+    pub fn held(&self) -> Option<Piece> {
+        self.held
     }
 
     pub fn tick<R: RngCore>(&mut self, random: &mut Random<R>) {
+        self.randomizer.fill(random);
+
         // next-level condition and switch
         if let Some((_, timer)) = &mut self.transiton {
             timer.tick();
@@ -196,6 +603,14 @@ impl Game {
             return;
         }
         if let Some(row) = obstable {
+            // the blade is stuck until the row is cleared
+            match &mut self.stuck {
+                Some((stuck, timer)) if *stuck == row => timer.tick(),
+                _ => {
+                    self.stuck = Some((row, Timer::infinite()));
+                    self.ramp_timer.reset();
+                }
+            }
             self.blade_hits_row(row);
         }
 
@@ -205,34 +620,84 @@ impl Game {
             return;
         }
 
-        // increase piece drop progression
-        if !self.drop_timer.step() {
+        if self.stuck.is_some() {
+            self.ramp_timer.tick();
+        }
+
+        // the landed piece is placed once the lock delay is over
+        if let Some(timer) = &mut self.lock
+            && timer.step()
+        {
+            self.lock = None;
+            // it may have slid off the edge in the meantime
+            if self.resting() {
+                self.place_piece(random);
+                return;
+            }
+        }
+
+        // increase piece drop progression, dropping on purpose doesn't wait for gravity
+        let dropping = self.soft_drop || self.hard_drop.is_some();
+        if !self.drop_timer.step() && !dropping {
             return;
         }
 
         // collision detection
-        for _ in 0..self.drop_speed {
+        let drop_speed = self.drop_speed.saturating_add(self.ramped() as i32);
+        let drop_speed = if self.soft_drop {
+            drop_speed.max(SOFT_DROP_SPEED)
+        } else {
+            drop_speed
+        };
+        for _ in 0..drop_speed {
             let collision = !self.try_to(|game| {
                 game.drop = game.drop.saturating_add(1);
             });
 
             if collision {
-                // next piece
-                if self.persist_piece() {
-                    self.spawn_next_piece(random);
+                // hard dropped pieces are placed right away
+                if self.hard_drop.is_none()
+                    && let Some(lock_delay) = self.lock_delay
+                {
+                    self.lock.get_or_insert(Timer::new(lock_delay.ticks));
                     break;
-                } else {
-                    // game over
-                    self.switch_to(SwitchTo::GameOver(self.level));
+                }
+                if !self.place_piece(random) {
                     return;
                 }
+                break;
             }
+            // falling again
+            self.lock = None;
         }
 
         // check completed rows
         self.check_completed_rows();
     }
 
+    /// Place the piece where it is, clear completed rows and spawn the next one,
+    /// returns false on game over
+    fn place_piece<R: RngCore>(&mut self, random: &mut Random<R>) -> bool {
+        if !self.persist_piece() {
+            self.switch_to(SwitchTo::GameOver(self.level));
+            return false;
+        }
+        self.pieces_placed = self.pieces_placed.saturating_add(1);
+        if let Some(from) = self.hard_drop.take() {
+            let rows = (self.drop - from) / LANE_WIDTH as i32;
+            self.score.hard_dropped(rows.max(0) as u32);
+        }
+        // the rows make room before the next piece needs it
+        self.check_completed_rows();
+        self.spawn_next_piece(random);
+        // no room for the next piece
+        if self.collides() {
+            self.switch_to(SwitchTo::GameOver(self.level));
+            return false;
+        }
+        true
+    }
+
     fn switch_to(&mut self, target: SwitchTo) {
         self.transiton.get_or_insert_with(|| {
             (
@@ -245,12 +710,19 @@ impl Game {
         });
     }
 
+    /// Returns true if the level is over, even if the transition isn't due yet
+    #[inline]
+    pub fn in_transition(&self) -> bool {
+        self.transiton.is_some()
+    }
+
     pub fn transition(&self) -> Option<SwitchTo> {
         let (target, timer) = self.transiton.as_ref()?;
         timer.is_due().then_some(*target)
     }
 
     fn check_completed_rows(&mut self) {
+        let mut cleared = 0;
         for y in 0..NUM_ROWS {
             let y = y as usize;
 
@@ -272,6 +744,17 @@ impl Game {
 
             self.clear_row(y);
             self.shift_previous_rows(y);
+            cleared += 1;
+        }
+
+        if cleared > 0 {
+            self.score.cleared_rows(cleared);
+        }
+        if let Some((row, timer)) = self.stuck
+            && self.lanes[0][row].is_none()
+        {
+            self.score.freed_blade(timer.get());
+            self.stuck = None;
         }
     }
 
@@ -303,69 +786,47 @@ impl Game {
 
     fn persist_piece(&mut self) -> bool {
         let mut gameover = false;
-        let offset_y = (self.drop / LANE_WIDTH as i32) + 1;
-        for (x, lane) in self.piece.tiles().iter().enumerate() {
-            let x = self.lane as usize + x;
-            for (y, tile) in lane.iter().enumerate() {
-                // if there's nothing in the piece grid
-                if !tile {
-                    continue;
-                }
-
-                // check gameover condition
-                let y = offset_y + y as i32;
-                if y <= 0 {
-                    gameover = true;
-                }
+        let mut lanes = self.lanes;
+        for (x, y) in self.piece_tiles() {
+            // check gameover condition
+            if y <= 0 {
+                gameover = true;
+            }
 
-                // only consider piece tiles that are visible
-                let Ok(y) = usize::try_from(y) else {
-                    continue;
-                };
+            // only consider piece tiles that are visible
+            let Ok(y) = usize::try_from(y) else {
+                continue;
+            };
 
-                // check for collision
-                let Some(lane) = self.lanes.get_mut(x) else {
-                    continue;
-                };
-                let Some(tile) = lane.get_mut(y) else {
-                    continue;
-                };
-                *tile = Some(Tile { wall: false });
-            }
+            // check for collision
+            let Some(lane) = lanes.get_mut(x as usize) else {
+                continue;
+            };
+            let Some(tile) = lane.get_mut(y) else {
+                continue;
+            };
+            *tile = Some(Tile { wall: false });
         }
+        self.lanes = lanes;
         !gameover
     }
 
     pub fn spawn_next_piece<R: RngCore>(&mut self, random: &mut Random<R>) {
-        let next_piece = {
-            let mut current = Some(self.piece.piece);
-            loop {
-                let random = (random.squeeze() as u8) % 8;
-                let mut next = match random {
-                    0 => continue, // Removed: Some(Piece::O),
-                    1 => Some(Piece::I),
-                    2 => Some(Piece::J),
-                    3 => Some(Piece::L),
-                    4 => Some(Piece::T),
-                    5 => Some(Piece::S),
-                    6 => Some(Piece::T), // Removed: Some(Piece::Z),
-                    _ => None,
-                };
-                // reduce the chance of a duplicate piece, but not impossible
-                if next == current.take() {
-                    next = None;
-                }
-                if let Some(piece) = next {
-                    break piece;
-                }
-            }
-        };
+        let next_piece = self.randomizer.next(random);
+        self.spawn(next_piece);
+        self.held_this_drop = false;
+    }
 
-        self.piece = next_piece.into_grid();
+    fn spawn(&mut self, piece: Piece) {
+        self.piece = piece.into_grid();
 
         self.lane = INITIAL_LANE;
         self.drop = -(self.piece.lowest_point() as i32 * LANE_WIDTH as i32);
-        self.drop_speed = 1; // TODO: this may get faster over time
+        self.hard_drop = None;
+        self.lock = None;
+        self.lock_resets = 0;
+        self.up_turns = [None; 2];
+        self.drop_speed = self.gravity.pixels as i32;
     }
 
     /// lowest possible number can be 1
@@ -392,13 +853,23 @@ impl Game {
         (None, i32::MAX)
     }
 
+    /// Obstacles in the blade lane that are still in the way
+    pub fn obstacles_remaining(&self) -> u32 {
+        self.lanes[0].iter().flatten().count() as u32
+    }
+
     pub fn blade_hits_row(&mut self, row: usize) {
+        let mut broken = false;
         for idx in [0, 1] {
             let tile = &mut self.lanes[idx][row];
             if let Some(tile) = tile {
+                broken |= tile.wall;
                 tile.wall = false;
             }
         }
+        if broken {
+            self.score.broke_tough();
+        }
     }
 
     pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
@@ -443,6 +914,22 @@ impl Game {
         .draw(display)
         .unwrap();
 
+        // render hud
+        Hud {
+            level: self.level + 1,
+            score: self.score.total(),
+            obstacles: self.obstacles_remaining(),
+            held: self.held,
+            preview: {
+                let mut preview = [None; randomizer::MAX_PREVIEW];
+                for (slot, piece) in preview.iter_mut().zip(self.preview()) {
+                    *slot = Some(piece);
+                }
+                preview
+            },
+        }
+        .render(display, &self.blade);
+
         // render narrator
         if let Some(narrator) = &self.narrator {
             narrator.render(display);
@@ -457,3 +944,20 @@ impl Game {
         }
     }
 }
+
+/// Text representation of the board, `#` are walls and `o` are tiles
+impl fmt::Display for Game {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for row in 0..NUM_ROWS as usize {
+            for lane in &self.lanes {
+                f.write_str(match lane[row] {
+                    Some(Tile { wall: true }) => "#",
+                    Some(Tile { wall: false }) => "o",
+                    None => ".",
+                })?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
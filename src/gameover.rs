@@ -1,15 +1,21 @@
 use crate::gfx;
+use crate::score::Score;
 use core::fmt::Debug;
 use embedded_graphics::{
     draw_target::DrawTarget,
     mono_font::MonoTextStyle,
     pixelcolor::BinaryColor,
     prelude::*,
-    text::{Baseline, Text},
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
 };
 
-const GAMEOVER_Y_OFFSET: i32 = 20;
+const GAMEOVER_Y_OFFSET: i32 = 6;
+const LEVEL_Y_OFFSET: i32 = 22;
+const LEVEL_LINE_HEIGHT: i32 = 8;
 const SCORE_Y_OFFSET: i32 = 50;
+const SCORE_LINE_HEIGHT: i32 = 7;
+const SCORE_X_OFFSET: i32 = 6;
+const POINTS_X_OFFSET: i32 = gfx::DISPLAY_WIDTH - 6;
 const MENU_Y_OFFSET: i32 = 95;
 const QUIT_Y_OFFSET: i32 = MENU_Y_OFFSET;
 const RESTART_Y_OFFSET: i32 = MENU_Y_OFFSET + 10;
@@ -31,14 +37,16 @@ impl Decision {
 }
 
 pub struct Gameover {
-    score: u32,
+    level: u32,
+    score: Score,
     decision: Decision,
     confirmed: bool,
 }
 
 impl Gameover {
-    pub const fn new(score: u32) -> Self {
+    pub const fn new(level: u32, score: Score) -> Self {
         Self {
+            level,
             score,
             decision: Decision::Quit,
             confirmed: false,
@@ -90,24 +98,61 @@ impl Gameover {
         // render game over text
         Self::render_centered("Game over", GAMEOVER_Y_OFFSET, gfx::BIG_TEXT_STYLE, display);
 
-        // render score
-        Self::render_centered("You held", SCORE_Y_OFFSET, gfx::TEXT_STYLE, display);
+        // render level
+        Self::render_centered("You held", LEVEL_Y_OFFSET, gfx::TEXT_STYLE, display);
 
         let mut buf = itoa::Buffer::new();
-        let buf = buf.format(self.score);
-        Self::render_centered(buf, SCORE_Y_OFFSET + 10, gfx::TEXT_STYLE, display);
+        let buf = buf.format(self.level);
+        Self::render_centered(
+            buf,
+            LEVEL_Y_OFFSET + LEVEL_LINE_HEIGHT,
+            gfx::TEXT_STYLE,
+            display,
+        );
 
         Self::render_centered(
-            if self.score == 1 {
+            if self.level == 1 {
                 "CEO accountable"
             } else {
                 "CEOs accountable"
             },
-            SCORE_Y_OFFSET + 20,
+            LEVEL_Y_OFFSET + 2 * LEVEL_LINE_HEIGHT,
             gfx::TEXT_STYLE,
             display,
         );
 
+        // render score
+        let score = &self.score;
+        let lines = [
+            ("rows", score.rows),
+            ("combo", score.combo),
+            ("blade", score.blade),
+            ("tough", score.tough),
+            ("drop", score.drop),
+            ("total", score.total()),
+        ];
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        for (num, (label, points)) in lines.into_iter().enumerate() {
+            let y = SCORE_Y_OFFSET + num as i32 * SCORE_LINE_HEIGHT;
+            Text::with_baseline(
+                label,
+                Point::new(SCORE_X_OFFSET, y),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            let mut buf = itoa::Buffer::new();
+            let buf = buf.format(points);
+            Text::with_text_style(buf, Point::new(POINTS_X_OFFSET, y), gfx::TEXT_STYLE, right)
+                .draw(display)
+                .unwrap();
+        }
+
         // render options
         Text::with_baseline(
             "Return to 9-5",
@@ -0,0 +1,274 @@
+//! Procedurally generated levels, played once the campaign from `levels.txt` is done.
+//!
+//! Every level is derived from its level number, so the same number always
+//! generates the same level and replays stay deterministic. The difficulty
+//! rises with the level number: more obstacles, higher up, and more of them
+//! tough. A candidate is only offered if the bot manages to win it within the
+//! piece budget, otherwise the next candidate is tried.
+//!
+//! The pieces of a generated level are dealt from a seed as well, so the bot
+//! plays exactly the pieces the player gets. Letting the bot play takes a
+//! while, `Search` does it a piece at a time so it can run between frames.
+
+use crate::bot;
+use crate::game::{Game, SwitchTo};
+use crate::input::Button;
+use crate::levels::Level;
+use crate::random::{Random, Seeded};
+use rand_core::{RngCore, SeedableRng};
+
+/// Highest row an obstacle is placed in, higher than this gets unfair
+pub const MAX_ROW: u32 = 12;
+/// Most obstacles in a single level
+pub const MAX_OBSTACLES: usize = 4;
+/// Most tough obstacles in a single level
+pub const MAX_TOUGH: usize = 2;
+/// Pieces the bot may use to win a level before it's considered too hard
+pub const PIECE_BUDGET: u32 = 80;
+/// Candidates that are checked before trying easier ones
+const ATTEMPTS: u32 = 4;
+/// Candidates that are checked at most, then the `loop` level is played
+pub const MAX_CANDIDATES: u32 = 16;
+/// Ticks one piece may take on average, including the blade moving
+const TICKS_PER_PIECE: u32 = 200;
+/// Most ticks played by a single `Search::step`
+const TICKS_PER_STEP: u32 = 50;
+/// Keep the seeds apart from the ones used by replays
+const SEED_SALT: u64 = 0x6368_6f70_6368_6f70;
+/// Keep the piece sequence apart from the candidates
+const PIECE_SALT: u64 = 0x7069_6563_6573_2121;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Obstacle {
+    Normal,
+    Tough,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Generated {
+    /// Obstacles by row, counted from the bottom, `rows[0]` is row 1
+    rows: [Option<Obstacle>; MAX_ROW as usize],
+}
+
+impl Generated {
+    /// Place obstacles without checking them, `depth` is the number of
+    /// levels since the end of the campaign
+    pub fn candidate<R: RngCore>(depth: u32, random: &mut R) -> Self {
+        let count = usize::min(1 + depth as usize / 6, MAX_OBSTACLES);
+        let tough = usize::min(depth as usize / 8, MAX_TOUGH);
+        // start low, the blade has to wait for less pieces there
+        let max_row = u32::min(4 + depth / 2, MAX_ROW);
+
+        let mut rows = [None; MAX_ROW as usize];
+        let mut placed = 0;
+        while placed < count {
+            let row = (random.next_u32() % max_row) as usize;
+            if rows[row].is_some() {
+                continue;
+            }
+            rows[row] = Some(if placed < tough {
+                Obstacle::Tough
+            } else {
+                Obstacle::Normal
+            });
+            placed += 1;
+        }
+
+        Generated { rows }
+    }
+
+    /// Find a winnable level for the given level number, all at once. This
+    /// takes a while, the game uses `Search` to spread it over many ticks.
+    ///
+    /// Returns `None` if none of the candidates could be won by the bot.
+    pub fn generate(level: u32) -> Option<Self> {
+        Search::new(level).finish()
+    }
+
+    /// Obstacles with their row, counted from the bottom
+    pub fn obstacles(&self) -> impl Iterator<Item = (u32, Obstacle)> + '_ {
+        self.rows
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, obstacle)| Some((idx as u32 + 1, (*obstacle)?)))
+    }
+
+    pub fn into_game(self, level: u32) -> Game {
+        let seed = u64::from(level) ^ PIECE_SALT;
+        let mut game = Game::new(level).with_piece_seed(seed);
+        for (row, obstacle) in self.obstacles() {
+            match obstacle {
+                Obstacle::Normal => game.add_obstacle_at_row(row),
+                Obstacle::Tough => game.add_tough_obstacle_at_row(row),
+            }
+        }
+        game
+    }
+}
+
+/// Looks for a winnable level by letting the bot play the candidates
+///
+/// If none of them can be won, easier ones are tried. Each step places at
+/// most one piece, the search gives up after `MAX_CANDIDATES`.
+#[derive(Clone)]
+pub struct Search {
+    level: u32,
+    /// Picks the candidates
+    random: Random<Seeded>,
+    depth: u32,
+    /// Candidates checked at the current depth
+    attempts: u32,
+    /// Candidates checked in total
+    checked: u32,
+    candidate: Generated,
+    /// The candidate, played by the bot
+    trial: Game,
+    /// Ticks the bot played the candidate
+    ticks: u32,
+    /// Piece the bot already decided on
+    planned: Option<u32>,
+    found: Option<Generated>,
+    done: bool,
+}
+
+impl Search {
+    pub fn new(level: u32) -> Self {
+        let mut random = Random::seed_from_u64(u64::from(level) ^ SEED_SALT);
+        let depth = level.saturating_sub(Level::CAMPAIGN);
+        let candidate = Generated::candidate(depth, &mut random);
+        Search {
+            level,
+            random,
+            depth,
+            attempts: 0,
+            checked: 0,
+            candidate,
+            trial: candidate.into_game(level),
+            ticks: 0,
+            planned: None,
+            found: None,
+            done: false,
+        }
+    }
+
+    #[inline]
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    #[inline]
+    pub fn done(&self) -> bool {
+        self.done
+    }
+
+    /// The winnable level, `None` while searching or if there was none
+    #[inline]
+    pub fn found(&self) -> Option<Generated> {
+        self.found
+    }
+
+    /// The level that was found, or the `loop` level if there was none
+    pub fn game(&self) -> Game {
+        match self.found {
+            Some(generated) => generated.into_game(self.level),
+            None => Game::from_level(self.level),
+        }
+    }
+
+    /// Search until it's done
+    pub fn finish(mut self) -> Option<Generated> {
+        while !self.done {
+            self.step();
+        }
+        self.found
+    }
+
+    /// Let the bot place the next piece of the current candidate
+    pub fn step(&mut self) {
+        if self.done {
+            return;
+        }
+        match self.play() {
+            Some(true) => {
+                self.found = Some(self.candidate);
+                self.done = true;
+            }
+            Some(false) => self.next_candidate(),
+            None => (),
+        }
+    }
+
+    /// Returns if the bot won once it's decided
+    fn play(&mut self) -> Option<bool> {
+        let game = &mut self.trial;
+        for _ in 0..TICKS_PER_STEP {
+            if let Some(switch) = game.transition() {
+                return Some(switch == SwitchTo::NextLevel(self.level + 1));
+            }
+            if game.pieces_placed() > PIECE_BUDGET || self.ticks >= PIECE_BUDGET * TICKS_PER_PIECE {
+                return Some(false);
+            }
+            self.ticks += 1;
+
+            // one decision per piece is enough, nothing moves the piece but the bot
+            let decide = !game.in_transition() && self.planned != Some(game.pieces_placed());
+            if decide {
+                self.planned = Some(game.pieces_placed());
+                if let Some(placement) = bot::best_placement(game) {
+                    place(game, placement);
+                }
+            }
+
+            // the pieces come from the level's seed, this random isn't used
+            game.tick(&mut self.random);
+            if decide {
+                break;
+            }
+        }
+        None
+    }
+
+    fn next_candidate(&mut self) {
+        self.checked += 1;
+        self.attempts += 1;
+        if self.checked >= MAX_CANDIDATES {
+            self.done = true;
+            return;
+        }
+        if self.attempts >= ATTEMPTS {
+            if self.depth == 0 {
+                self.done = true;
+                return;
+            }
+            self.depth /= 2;
+            self.attempts = 0;
+        }
+
+        self.candidate = Generated::candidate(self.depth, &mut self.random);
+        self.trial = self.candidate.into_game(self.level);
+        self.ticks = 0;
+        self.planned = None;
+    }
+}
+
+/// Press all buttons to get the current piece into place and drop it
+fn place(game: &mut Game, mut placement: bot::Placement) {
+    loop {
+        let lane = game.lane();
+        let tiles = *game.piece().tiles();
+        match placement.next_button(game) {
+            Button::Up => {
+                game.button_up();
+                placement.rotations -= 1;
+            }
+            Button::Left => game.button_left(),
+            Button::Right => game.button_right(),
+            _ => break,
+        }
+        // blocked, drop it wherever it is
+        if game.lane() == lane && *game.piece().tiles() == tiles {
+            break;
+        }
+    }
+    game.hard_drop();
+}
@@ -26,6 +26,12 @@ pub struct Blade {
     speed: Timer,
 }
 
+impl Default for Blade {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Blade {
     pub const fn new() -> Self {
         Blade {
@@ -47,6 +53,12 @@ impl Blade {
         false
     }
 
+    /// Height of the bottom edge, it's above the display while the blade is
+    /// still coming in
+    pub fn depth(&self) -> i32 {
+        self.bottom_right.y
+    }
+
     pub fn points(&self) -> [Point; 7] {
         let bottom_right = self.bottom_right;
         [
@@ -0,0 +1,166 @@
+//! Level, score, progress, the held and the upcoming pieces in the strip left
+//! of the board.
+//!
+//! The blade moves through the same strip, so the HUD sits at the bottom
+//! while the blade is above it and moves to the top once the blade got
+//! further down.
+
+use crate::game;
+use crate::gfx;
+use crate::gfx::blade::{self, Blade};
+use crate::pieces::Piece;
+use crate::randomizer::MAX_PREVIEW;
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// Width of the strip, up to the first lane
+pub const WIDTH: i32 = game::LANE_OFFSET.x;
+pub const HEIGHT: i32 = LINE_HEIGHT
+    + GAP
+    + 2 * LINE_HEIGHT
+    + GAP
+    + LINE_HEIGHT
+    + GAP
+    + PIECE_HEIGHT
+    + GAP
+    + METER_HEIGHT;
+
+const LINE_HEIGHT: i32 = 7;
+const GAP: i32 = 1;
+const LEFT: i32 = 1;
+const RIGHT: i32 = WIDTH - 2;
+/// Digits per line, longer scores are split over two lines
+const DIGITS: usize = 3;
+const MAX_SCORE: u32 = 999_999;
+const ICON_SIZE: u32 = 5;
+/// Levels with more digits are shown without the label
+const MAX_LABELED_LEVEL: u32 = 99;
+const METER_WIDTH: u32 = 4;
+const METER_HEIGHT: i32 = MAX_PREVIEW as i32 * PREVIEW_STEP - GAP;
+/// Size of a tile in the preview, the pieces are shown next to the meter
+const PREVIEW_TILE: u32 = 2;
+const PREVIEW_X_OFFSET: i32 = LEFT + METER_WIDTH as i32 + 1;
+/// Pieces are two tiles high when they spawn
+const PIECE_HEIGHT: i32 = 2 * PREVIEW_TILE as i32;
+const PREVIEW_STEP: i32 = PIECE_HEIGHT + GAP;
+
+// one of the two positions is always clear of the blade
+static_assertions::const_assert!((2 * HEIGHT + blade::HEIGHT as i32) < gfx::DISPLAY_HEIGHT);
+static_assertions::const_assert_eq!(WIDTH, 15);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hud {
+    /// Level as shown to the player, starting at 1
+    pub level: u32,
+    pub score: u32,
+    /// Obstacles left in the blade lane
+    pub obstacles: u32,
+    /// Piece in the hold slot, it's shown left-aligned above the preview
+    pub held: Option<Piece>,
+    /// Upcoming pieces, next one first
+    pub preview: [Option<Piece>; MAX_PREVIEW],
+}
+
+impl Hud {
+    /// Top of the HUD, depending on where the blade currently is
+    pub fn y_offset(blade: &Blade) -> i32 {
+        let bottom = blade.points().iter().map(|p| p.y).max().unwrap_or(0);
+        if bottom < gfx::DISPLAY_HEIGHT - HEIGHT {
+            gfx::DISPLAY_HEIGHT - HEIGHT
+        } else {
+            0
+        }
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D, blade: &Blade)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+        let mut y = Self::y_offset(blade);
+
+        // render level
+        if self.level <= MAX_LABELED_LEVEL {
+            Text::with_baseline("L", Point::new(LEFT, y), gfx::TEXT_STYLE, Baseline::Top)
+                .draw(display)
+                .unwrap();
+        }
+        let mut buf = itoa::Buffer::new();
+        let level = buf.format(self.level);
+        Text::with_text_style(level, Point::new(RIGHT, y), gfx::TEXT_STYLE, right)
+            .draw(display)
+            .unwrap();
+        y += LINE_HEIGHT + GAP;
+
+        // render score, the last digits go on the second line
+        let mut buf = itoa::Buffer::new();
+        let score = buf.format(self.score.min(MAX_SCORE));
+        let (high, low) = score.split_at(score.len().saturating_sub(DIGITS));
+        for line in [high, low] {
+            Text::with_text_style(line, Point::new(RIGHT, y), gfx::TEXT_STYLE, right)
+                .draw(display)
+                .unwrap();
+            y += LINE_HEIGHT;
+        }
+        y += GAP;
+
+        // render remaining obstacles next to a small tile
+        Rectangle::new(Point::new(LEFT, y), Size::new(ICON_SIZE, ICON_SIZE))
+            .into_styled(gfx::WHITE_LINE)
+            .draw(display)
+            .unwrap();
+        let mut buf = itoa::Buffer::new();
+        let obstacles = buf.format(self.obstacles);
+        Text::with_text_style(obstacles, Point::new(RIGHT, y), gfx::TEXT_STYLE, right)
+            .draw(display)
+            .unwrap();
+        y += LINE_HEIGHT + GAP;
+
+        // render held piece, its top row in the grid is empty
+        if let Some(piece) = self.held {
+            piece.into_grid().render_small(
+                display,
+                Point::new(LEFT, y - PREVIEW_TILE as i32),
+                PREVIEW_TILE,
+            );
+        }
+        y += PIECE_HEIGHT + GAP;
+
+        // render blade depth, filled from the top
+        Rectangle::new(
+            Point::new(LEFT, y),
+            Size::new(METER_WIDTH, METER_HEIGHT as u32),
+        )
+        .into_styled(gfx::WHITE_LINE)
+        .draw(display)
+        .unwrap();
+        let depth = blade.depth().clamp(0, gfx::DISPLAY_HEIGHT);
+        let filled = depth * (METER_HEIGHT - 2) / gfx::DISPLAY_HEIGHT;
+        Rectangle::new(
+            Point::new(LEFT + 1, y + 1),
+            Size::new(METER_WIDTH - 2, filled as u32),
+        )
+        .into_styled(gfx::WHITE)
+        .draw(display)
+        .unwrap();
+
+        // render upcoming pieces
+        for piece in self.preview.iter().flatten() {
+            piece.into_grid().render_small(
+                display,
+                Point::new(PREVIEW_X_OFFSET, y - PREVIEW_TILE as i32),
+                PREVIEW_TILE,
+            );
+            y += PREVIEW_STEP;
+        }
+    }
+}
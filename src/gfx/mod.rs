@@ -1,12 +1,18 @@
 #![allow(unused)] // TODO
 
 pub mod blade;
+pub mod hud;
 pub mod tile;
 
+use core::fmt::Debug;
 use embedded_graphics::{
+    framebuffer::{self, buffer_size},
+    image::GetPixel,
     mono_font::{MonoFont, MonoTextStyle, MonoTextStyleBuilder, ascii},
-    pixelcolor::BinaryColor,
+    pixelcolor::{BinaryColor, raw::BigEndian, raw::RawU1},
+    prelude::*,
     primitives::PrimitiveStyle,
+    text::{Baseline, Text},
 };
 
 pub const DISPLAY_WIDTH: i32 = 64;
@@ -14,6 +20,16 @@ pub const DISPLAY_HEIGHT: i32 = 128;
 pub const UDISPLAY_WIDTH: u32 = DISPLAY_WIDTH as u32;
 pub const UDISPLAY_HEIGHT: u32 = DISPLAY_HEIGHT as u32;
 
+/// In-memory display, used to render frames outside of the device
+pub type Framebuffer = framebuffer::Framebuffer<
+    BinaryColor,
+    RawU1,
+    BigEndian,
+    { DISPLAY_WIDTH as usize },
+    { DISPLAY_HEIGHT as usize },
+    { buffer_size::<BinaryColor>(DISPLAY_WIDTH as usize, DISPLAY_HEIGHT as usize) },
+>;
+
 pub const WHITE: PrimitiveStyle<BinaryColor> = PrimitiveStyle::with_fill(BinaryColor::On);
 pub const BLACK: PrimitiveStyle<BinaryColor> = PrimitiveStyle::with_fill(BinaryColor::Off);
 
@@ -41,3 +57,24 @@ pub const fn text_vertical_center(total: i32, font: &MonoFont) -> i32 {
 pub const fn text_align_center(text: &str, total: i32, font: &MonoFont) -> i32 {
     centered(total, text.len() as u32 * font.character_size.width)
 }
+
+/// Lines of small text in the middle of the screen, e.g. for errors at startup
+pub fn message<D: DrawTarget<Color = BinaryColor>>(display: &mut D, lines: &[&str])
+where
+    <D as DrawTarget>::Error: Debug,
+{
+    let line_height = TEXT_STYLE.font.character_size.height;
+    let top = centered(DISPLAY_HEIGHT, lines.len() as u32 * line_height);
+    for (num, line) in lines.iter().enumerate() {
+        let x = text_align_center(line, DISPLAY_WIDTH, TEXT_STYLE.font);
+        let y = top + (num as u32 * line_height) as i32;
+        Text::with_baseline(line, Point::new(x, y), TEXT_STYLE, Baseline::Top)
+            .draw(display)
+            .unwrap();
+    }
+}
+
+/// Read a single pixel of a rendered frame, pixels outside the display are off
+pub fn pixel(frame: &Framebuffer, point: Point) -> bool {
+    frame.as_image().pixel(point) == Some(BinaryColor::On)
+}
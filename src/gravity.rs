@@ -0,0 +1,97 @@
+//! How fast pieces fall.
+//!
+//! Pieces fall a few pixels every few ticks, depending on the level. Levels
+//! past the end of the table keep the speed of its last entry. While the
+//! blade is stuck on the same row, the speed can ramp up a little, to push
+//! the player to free it.
+//!
+//! Once a piece lands it isn't placed right away, the lock delay gives the
+//! player some time to slide or rotate it into a gap.
+
+/// Pieces fall `pixels` every `delay` ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gravity {
+    pub delay: u8,
+    pub pixels: u8,
+}
+
+impl Gravity {
+    pub const fn new(delay: u8, pixels: u8) -> Self {
+        Gravity { delay, pixels }
+    }
+
+    /// Gravity of the given level
+    pub const fn of_level(level: u32) -> Self {
+        let idx = if (level as usize) < TABLE.len() {
+            level as usize
+        } else {
+            TABLE.len() - 1
+        };
+        TABLE[idx]
+    }
+}
+
+/// Gravity by level, a tile is 6 pixels high
+pub const TABLE: &[Gravity] = &[
+    Gravity::new(1, 1),
+    Gravity::new(1, 1),
+    Gravity::new(1, 1),
+    Gravity::new(3, 4),
+    Gravity::new(3, 4),
+    Gravity::new(2, 3),
+    Gravity::new(2, 3),
+    Gravity::new(1, 2),
+    Gravity::new(1, 2),
+    Gravity::new(2, 5),
+    Gravity::new(1, 3),
+];
+
+/// Extra pixels per step while the blade is stuck on the same row
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ramp {
+    /// Ticks the blade is stuck before the first increase
+    pub after: u8,
+    /// Ticks between increases
+    pub every: u8,
+    /// Most extra pixels
+    pub max: u8,
+}
+
+impl Ramp {
+    /// Extra pixels after the blade was stuck for this many ticks
+    pub const fn extra(&self, stuck: u8) -> u8 {
+        if stuck < self.after {
+            return 0;
+        }
+        let steps = (stuck - self.after) / if self.every > 0 { self.every } else { 1 } + 1;
+        if steps < self.max { steps } else { self.max }
+    }
+}
+
+/// Ramp used by every level, `None` keeps the speed of the level
+pub const RAMP: Option<Ramp> = Some(Ramp {
+    after: 100,
+    every: 50,
+    max: 2,
+});
+
+/// Ticks a landed piece waits before it's placed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LockDelay {
+    pub ticks: u8,
+    /// How often moving or rotating the piece starts the delay over
+    pub resets: u8,
+}
+
+impl LockDelay {
+    pub const DEFAULT: Self = LockDelay {
+        ticks: 10,
+        resets: 15,
+    };
+}
+
+impl Default for LockDelay {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,207 @@
+//! Top 10 table, kept in flash, and the screen that shows it.
+
+use crate::gfx;
+use crate::storage::{self, Flash, Store};
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+pub const NUM_ENTRIES: usize = 10;
+/// Key of the table in the store
+pub const KEY: u8 = 1;
+
+const ENTRY_SIZE: usize = 7;
+const TITLE_Y_OFFSET: i32 = 8;
+const TABLE_Y_OFFSET: i32 = 28;
+const LINE_HEIGHT: i32 = 9;
+/// Right edge of the rank, it's right-aligned like the score
+const RANK_X_OFFSET: i32 = 10;
+const INITIALS_X_OFFSET: i32 = 16;
+const SCORE_X_OFFSET: i32 = gfx::DISPLAY_WIDTH - 3;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Entry {
+    /// Uppercase ascii letters
+    pub initials: [u8; 3],
+    pub score: u32,
+}
+
+impl Entry {
+    fn initials(&self) -> &str {
+        core::str::from_utf8(&self.initials).unwrap_or("???")
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HighScores {
+    /// Sorted by score, highest first
+    entries: [Option<Entry>; NUM_ENTRIES],
+}
+
+impl Default for HighScores {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HighScores {
+    pub const fn new() -> Self {
+        HighScores {
+            entries: [None; NUM_ENTRIES],
+        }
+    }
+
+    /// Read the table from the store, an empty table if there's none yet
+    pub fn load<F: Flash>(store: &Store<F>) -> Self {
+        store.get(KEY).map(Self::from_bytes).unwrap_or_default()
+    }
+
+    pub fn save<F: Flash>(&self, store: &mut Store<F>) -> Result<(), storage::Error<F::Error>> {
+        let mut buf = [0; NUM_ENTRIES * ENTRY_SIZE];
+        let mut len = 0;
+        for entry in self.entries() {
+            buf[len..len + 3].copy_from_slice(&entry.initials);
+            buf[len + 3..len + ENTRY_SIZE].copy_from_slice(&entry.score.to_le_bytes());
+            len += ENTRY_SIZE;
+        }
+        store.set(KEY, &buf[..len])?;
+        store.commit()
+    }
+
+    /// Entries that don't make sense are dropped
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut table = Self::new();
+        for chunk in bytes.chunks_exact(ENTRY_SIZE) {
+            let mut initials = [0; 3];
+            initials.copy_from_slice(&chunk[..3]);
+            if !initials.iter().all(u8::is_ascii_uppercase) {
+                continue;
+            }
+            let score = u32::from_le_bytes([chunk[3], chunk[4], chunk[5], chunk[6]]);
+            table.insert(Entry { initials, score });
+        }
+        table
+    }
+
+    pub fn entries(&self) -> impl Iterator<Item = &Entry> {
+        self.entries.iter().flatten()
+    }
+
+    /// Returns true if the score would make it into the table
+    pub fn qualifies(&self, score: u32) -> bool {
+        score > 0
+            && self.entries[NUM_ENTRIES - 1]
+                .map(|last| score > last.score)
+                .unwrap_or(true)
+    }
+
+    /// Add an entry, returns its rank (starting at 0) if it made it into the table
+    pub fn insert(&mut self, entry: Entry) -> Option<usize> {
+        if !self.qualifies(entry.score) {
+            return None;
+        }
+        // equal scores keep their order, the older entry stays ahead
+        let rank = self
+            .entries
+            .iter()
+            .position(|slot| slot.is_none_or(|existing| entry.score > existing.score))?;
+        self.entries[rank..].rotate_right(1);
+        self.entries[rank] = Some(entry);
+        Some(rank)
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        let title = "Top 10";
+        let x = gfx::text_align_center(title, gfx::DISPLAY_WIDTH, gfx::BIG_TEXT_STYLE.font);
+        Text::with_baseline(
+            title,
+            Point::new(x, TITLE_Y_OFFSET),
+            gfx::BIG_TEXT_STYLE,
+            Baseline::Top,
+        )
+        .draw(display)
+        .unwrap();
+
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let y = TABLE_Y_OFFSET + idx as i32 * LINE_HEIGHT;
+
+            let mut buf = itoa::Buffer::new();
+            let rank = buf.format(idx + 1);
+            Text::with_text_style(rank, Point::new(RANK_X_OFFSET, y), gfx::TEXT_STYLE, right)
+                .draw(display)
+                .unwrap();
+
+            let Some(entry) = entry else {
+                Text::with_baseline(
+                    "---",
+                    Point::new(INITIALS_X_OFFSET, y),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+                continue;
+            };
+
+            Text::with_baseline(
+                entry.initials(),
+                Point::new(INITIALS_X_OFFSET, y),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            let mut buf = itoa::Buffer::new();
+            let score = buf.format(entry.score);
+            Text::with_text_style(score, Point::new(SCORE_X_OFFSET, y), gfx::TEXT_STYLE, right)
+                .draw(display)
+                .unwrap();
+        }
+    }
+}
+
+/// Screen that shows the table, any button goes back
+pub struct Table {
+    highscores: HighScores,
+    exit: bool,
+}
+
+impl Table {
+    pub const fn new(highscores: HighScores) -> Self {
+        Table {
+            highscores,
+            exit: false,
+        }
+    }
+
+    #[inline(always)]
+    pub fn button_pressed(&mut self) {
+        self.exit = true;
+    }
+
+    #[inline]
+    pub fn done(&self) -> bool {
+        self.exit
+    }
+
+    #[inline(always)]
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        self.highscores.render(display);
+    }
+}
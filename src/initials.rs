@@ -0,0 +1,136 @@
+//! Let the player enter three letters after a run that made it into the high scores.
+
+use crate::gfx;
+use crate::highscores::Entry;
+use crate::score::Score;
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Line,
+    text::{Baseline, Text},
+};
+
+const TITLE_Y_OFFSET: i32 = 20;
+const SCORE_Y_OFFSET: i32 = 45;
+const LETTERS_Y_OFFSET: i32 = 70;
+const LETTER_SPACING: i32 = 14;
+const CURSOR_PADDING: i32 = 2;
+const HINT_Y_OFFSET: i32 = 100;
+
+pub struct Initials {
+    level: u32,
+    score: Score,
+    letters: [u8; 3],
+    cursor: usize,
+    confirmed: bool,
+}
+
+impl Initials {
+    pub const fn new(level: u32, score: Score) -> Self {
+        Initials {
+            level,
+            score,
+            letters: *b"AAA",
+            cursor: 0,
+            confirmed: false,
+        }
+    }
+
+    #[inline]
+    pub const fn level(&self) -> u32 {
+        self.level
+    }
+
+    #[inline]
+    pub const fn score(&self) -> &Score {
+        &self.score
+    }
+
+    /// The finished entry, once the player confirmed it
+    pub fn entry(&self) -> Option<Entry> {
+        self.confirmed.then_some(Entry {
+            initials: self.letters,
+            score: self.score.total(),
+        })
+    }
+
+    /// next letter, wrapping around after Z
+    pub fn button_up(&mut self) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = if *letter >= b'Z' { b'A' } else { *letter + 1 };
+    }
+
+    /// previous letter, wrapping around before A
+    pub fn button_down(&mut self) {
+        let letter = &mut self.letters[self.cursor];
+        *letter = if *letter <= b'A' { b'Z' } else { *letter - 1 };
+    }
+
+    pub fn button_left(&mut self) {
+        self.cursor = self.cursor.saturating_sub(1);
+    }
+
+    pub fn button_right(&mut self) {
+        self.cursor = usize::min(self.cursor + 1, self.letters.len() - 1);
+    }
+
+    /// confirm entry
+    pub fn button_center(&mut self) {
+        self.confirmed = true;
+    }
+
+    fn render_centered<D: DrawTarget<Color = BinaryColor>>(text: &str, y: i32, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        let x = gfx::text_align_center(text, gfx::DISPLAY_WIDTH, gfx::TEXT_STYLE.font);
+        Text::with_baseline(text, Point::new(x, y), gfx::TEXT_STYLE, Baseline::Top)
+            .draw(display)
+            .unwrap();
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        Self::render_centered("New high score!", TITLE_Y_OFFSET, display);
+
+        let mut buf = itoa::Buffer::new();
+        let buf = buf.format(self.score.total());
+        Self::render_centered(buf, SCORE_Y_OFFSET, display);
+
+        // render letters
+        let font = gfx::BIG_TEXT_STYLE.font;
+        let width = font.character_size.width as i32;
+        let height = font.character_size.height as i32;
+        let total = (self.letters.len() as i32 - 1) * LETTER_SPACING + width;
+        let x_offset = gfx::centered(gfx::DISPLAY_WIDTH, total as u32);
+
+        let mut buf = [0; 4];
+        for (idx, letter) in self.letters.iter().enumerate() {
+            let x = x_offset + idx as i32 * LETTER_SPACING;
+            let letter = char::from(*letter).encode_utf8(&mut buf);
+            Text::with_baseline(
+                letter,
+                Point::new(x, LETTERS_Y_OFFSET),
+                gfx::BIG_TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            // render cursor
+            if idx == self.cursor {
+                let y = LETTERS_Y_OFFSET + height + CURSOR_PADDING;
+                Line::new(Point::new(x, y), Point::new(x + width - 1, y))
+                    .into_styled(gfx::WHITE_LINE)
+                    .draw(display)
+                    .unwrap();
+            }
+        }
+
+        Self::render_centered("press center", HINT_Y_OFFSET, display);
+    }
+}
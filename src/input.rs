@@ -0,0 +1,474 @@
+//! Buttons, and turning their raw state into events.
+//!
+//! The button state is sampled once per tick. After a button changed, its
+//! state is kept for a few ticks so bouncing contacts don't register as
+//! extra presses. Left and right repeat while they're held, so a piece can be
+//! slid across the board. The resulting events are queued until `Context`
+//! handles them.
+//!
+//! On top of that, gestures are recognized: holding a button, tapping it
+//! twice and pressing two buttons together. They're reported as events of
+//! their own, in addition to the plain presses and releases.
+
+use crate::ctx::Context;
+use crate::timer::Timer;
+use core::fmt;
+use core::str::FromStr;
+
+/// Ticks a button keeps its state after it changed, to ignore bouncing contacts
+pub const DEBOUNCE: u8 = 1;
+/// Most events waiting to be handled, any further ones are dropped
+pub const QUEUE_SIZE: usize = 16;
+/// Buttons that repeat while they're held
+const REPEATING: [Button; 2] = [Button::Left, Button::Right];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Button {
+    Up,
+    Down,
+    Left,
+    Right,
+    Center,
+}
+
+impl Button {
+    pub const ALL: [Button; 5] = [
+        Button::Up,
+        Button::Down,
+        Button::Left,
+        Button::Right,
+        Button::Center,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Button::Up => "up",
+            Button::Down => "down",
+            Button::Left => "left",
+            Button::Right => "right",
+            Button::Center => "center",
+        }
+    }
+
+    /// Press the button right away, without going through `Input`
+    #[inline]
+    pub fn press(&self, ctx: &mut Context) {
+        ctx.handle(ButtonEvent::new(*self, Kind::Pressed, ctx.ticks()));
+    }
+
+    /// Release the button right away, without going through `Input`
+    #[inline]
+    pub fn release(&self, ctx: &mut Context) {
+        ctx.handle(ButtonEvent::new(*self, Kind::Released, ctx.ticks()));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Kind {
+    Pressed,
+    /// The button is still held and repeats the press
+    Repeated,
+    Released,
+    /// The button is held for a while, reported once per press
+    LongPress,
+    /// The button was pressed again shortly after it was released
+    DoubleTap,
+    /// Pressed together with this button, which comes later in `Button::ALL`
+    Chord(Button),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ButtonEvent {
+    pub button: Button,
+    pub kind: Kind,
+    /// Tick of `Context` the event happened in
+    pub tick: u32,
+}
+
+impl ButtonEvent {
+    #[inline]
+    pub const fn new(button: Button, kind: Kind, tick: u32) -> Self {
+        ButtonEvent { button, kind, tick }
+    }
+}
+
+/// Delayed auto-shift, a held button repeats after `delay` ticks and then every `rate` ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AutoRepeat {
+    pub delay: u8,
+    pub rate: u8,
+}
+
+impl AutoRepeat {
+    pub const DEFAULT: Self = AutoRepeat { delay: 4, rate: 1 };
+}
+
+impl Default for AutoRepeat {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Thresholds of the gestures, in ticks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gestures {
+    /// How long a button needs to be held
+    pub long_press: u8,
+    /// Most time between releasing a button and pressing it again
+    pub double_tap: u8,
+    /// Most time between pressing two buttons
+    pub chord: u8,
+}
+
+impl Gestures {
+    pub const DEFAULT: Self = Gestures {
+        long_press: 6,
+        double_tap: 5,
+        chord: 2,
+    };
+}
+
+impl Default for Gestures {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
+
+/// Events in the order they happened
+#[derive(Debug, Clone)]
+pub struct EventQueue {
+    events: [ButtonEvent; QUEUE_SIZE],
+    len: usize,
+}
+
+impl Default for EventQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventQueue {
+    pub const fn new() -> Self {
+        EventQueue {
+            events: [ButtonEvent::new(Button::Center, Kind::Released, 0); QUEUE_SIZE],
+            len: 0,
+        }
+    }
+
+    /// Add an event, returns false if the queue is full and the event was dropped
+    pub fn push(&mut self, event: ButtonEvent) -> bool {
+        let Some(slot) = self.events.get_mut(self.len) else {
+            return false;
+        };
+        *slot = event;
+        self.len += 1;
+        true
+    }
+
+    /// Take the oldest event
+    pub fn pop(&mut self) -> Option<ButtonEvent> {
+        if self.len == 0 {
+            return None;
+        }
+        let event = self.events[0];
+        self.events.rotate_left(1);
+        self.len -= 1;
+        Some(event)
+    }
+
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    down: bool,
+    /// Changes are ignored until it's due
+    debounce: Timer,
+    /// Due when the next repeat is
+    repeat: Timer,
+    /// Ticks since the button was pressed
+    held: Timer,
+    /// Due when the long-press is, `None` once it was reported
+    long_press: Option<Timer>,
+    /// Once it's due, pressing the button again isn't a double-tap
+    double_tap: Timer,
+    /// The current press is the second tap, the next one starts over
+    second_tap: bool,
+}
+
+/// Turns the sampled button state into events
+#[derive(Debug, Clone)]
+pub struct Input {
+    debounce: u8,
+    auto_repeat: Option<AutoRepeat>,
+    gestures: Gestures,
+    states: [State; Button::ALL.len()],
+    queue: EventQueue,
+}
+
+impl Default for Input {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Input {
+    pub const fn new() -> Self {
+        let state = State {
+            down: false,
+            debounce: Timer::new(0),
+            repeat: Timer::new(0),
+            held: Timer::infinite(),
+            long_press: None,
+            double_tap: Timer::new(0),
+            second_tap: false,
+        };
+        Input {
+            debounce: DEBOUNCE,
+            auto_repeat: Some(AutoRepeat::DEFAULT),
+            gestures: Gestures::DEFAULT,
+            states: [state; Button::ALL.len()],
+            queue: EventQueue::new(),
+        }
+    }
+
+    /// Ticks a button keeps its state after it changed
+    pub const fn with_debounce(mut self, ticks: u8) -> Self {
+        self.debounce = ticks;
+        self
+    }
+
+    /// Repeat left and right while they're held, `None` disables it
+    pub const fn with_auto_repeat(mut self, auto_repeat: Option<AutoRepeat>) -> Self {
+        self.auto_repeat = auto_repeat;
+        self
+    }
+
+    pub const fn with_gestures(mut self, gestures: Gestures) -> Self {
+        self.gestures = gestures;
+        self
+    }
+
+    /// Sample every button once per tick, `is_down` returns the raw state of a button
+    pub fn update<F: FnMut(Button) -> bool>(&mut self, tick: u32, mut is_down: F) {
+        for button in Button::ALL {
+            let mut state = self.states[button as usize];
+            state.debounce.tick();
+            state.held.tick();
+            state.double_tap.tick();
+
+            let down = is_down(button);
+            if down != state.down && state.debounce.is_due() {
+                state.down = down;
+                state.debounce = Timer::new(self.debounce);
+                if down {
+                    self.pressed(button, &mut state, tick);
+                } else {
+                    state.long_press = None;
+                    if !core::mem::take(&mut state.second_tap) {
+                        state.double_tap = Timer::new(self.gestures.double_tap);
+                    }
+                    self.push(button, Kind::Released, tick);
+                }
+            } else if state.down {
+                self.held(button, &mut state, tick);
+            }
+            self.states[button as usize] = state;
+        }
+    }
+
+    fn pressed(&mut self, button: Button, state: &mut State, tick: u32) {
+        self.push(button, Kind::Pressed, tick);
+
+        if !state.double_tap.is_due() {
+            state.double_tap.set_due();
+            state.second_tap = true;
+            self.push(button, Kind::DoubleTap, tick);
+        }
+
+        // the other button was pressed shortly before, or earlier in this tick
+        for other in Button::ALL {
+            let other_state = &self.states[other as usize];
+            if other == button || !other_state.down || other_state.held.get() > self.gestures.chord
+            {
+                continue;
+            }
+            if (other as usize) < (button as usize) {
+                self.push(other, Kind::Chord(button), tick);
+            } else {
+                self.push(button, Kind::Chord(other), tick);
+            }
+        }
+
+        state.held = Timer::infinite();
+        state.long_press = Some(Timer::new(self.gestures.long_press));
+        if let Some(auto_repeat) = self.auto_repeat {
+            state.repeat = Timer::new(auto_repeat.delay);
+        }
+    }
+
+    fn held(&mut self, button: Button, state: &mut State, tick: u32) {
+        if let Some(timer) = &mut state.long_press {
+            timer.tick();
+            if timer.is_due() {
+                state.long_press = None;
+                self.push(button, Kind::LongPress, tick);
+            }
+        }
+
+        if REPEATING.contains(&button)
+            && let Some(auto_repeat) = self.auto_repeat
+        {
+            state.repeat.tick();
+            if state.repeat.is_due() {
+                state.repeat = Timer::new(auto_repeat.rate);
+                self.push(button, Kind::Repeated, tick);
+            }
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, button: Button, kind: Kind, tick: u32) {
+        self.queue.push(ButtonEvent::new(button, kind, tick));
+    }
+
+    /// The debounced state of a button
+    #[inline]
+    pub fn is_down(&self, button: Button) -> bool {
+        self.states[button as usize].down
+    }
+
+    /// Take the oldest event that wasn't handled yet
+    #[inline]
+    pub fn pop(&mut self) -> Option<ButtonEvent> {
+        self.queue.pop()
+    }
+
+    #[inline]
+    pub fn queue(&mut self) -> &mut EventQueue {
+        &mut self.queue
+    }
+}
+
+/// Which buttons drop the piece
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DropStyle {
+    /// Down drops faster while it's held, center drops the piece right away.
+    /// Holding center puts the piece on hold.
+    #[default]
+    Modern,
+    /// Down drops the piece right away, center puts it on hold
+    Classic,
+}
+
+impl DropStyle {
+    pub const ALL: [DropStyle; 2] = [DropStyle::Modern, DropStyle::Classic];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            DropStyle::Modern => "modern",
+            DropStyle::Classic => "classic",
+        }
+    }
+
+    /// The button that drops the piece right away
+    pub const fn hard_drop(&self) -> Button {
+        match self {
+            DropStyle::Modern => Button::Center,
+            DropStyle::Classic => Button::Down,
+        }
+    }
+}
+
+impl fmt::Display for Button {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownButton;
+
+impl FromStr for Button {
+    type Err = UnknownButton;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Button::ALL
+            .into_iter()
+            .find(|button| button.name() == s)
+            .ok_or(UnknownButton)
+    }
+}
+
+impl fmt::Display for ButtonEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (tick, button) = (self.tick, self.button);
+        match self.kind {
+            Kind::Pressed => write!(f, "{tick} {button}"),
+            Kind::Repeated => write!(f, "{tick} repeat {button}"),
+            Kind::Released => write!(f, "{tick} release {button}"),
+            Kind::LongPress => write!(f, "{tick} long {button}"),
+            Kind::DoubleTap => write!(f, "{tick} double {button}"),
+            Kind::Chord(other) => write!(f, "{tick} chord {button} {other}"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidEvent;
+
+impl FromStr for ButtonEvent {
+    type Err = InvalidEvent;
+
+    /// Parse the format used by `Display`, e.g. `12 release left`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut words = s.split_whitespace();
+        let tick = words.next().ok_or(InvalidEvent)?;
+        let words = [words.next(), words.next(), words.next(), words.next()];
+        let (kind, button) = match words {
+            [Some(button), None, None, None] => (Kind::Pressed, button),
+            [Some("repeat"), Some(button), None, None] => (Kind::Repeated, button),
+            [Some("release"), Some(button), None, None] => (Kind::Released, button),
+            [Some("long"), Some(button), None, None] => (Kind::LongPress, button),
+            [Some("double"), Some(button), None, None] => (Kind::DoubleTap, button),
+            [Some("chord"), Some(button), Some(other), None] => (
+                Kind::Chord(other.parse().map_err(|_| InvalidEvent)?),
+                button,
+            ),
+            _ => return Err(InvalidEvent),
+        };
+        Ok(ButtonEvent {
+            button: button.parse().map_err(|_| InvalidEvent)?,
+            kind,
+            tick: tick.parse().map_err(|_| InvalidEvent)?,
+        })
+    }
+}
+
+impl fmt::Display for DropStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownDropStyle;
+
+impl FromStr for DropStyle {
+    type Err = UnknownDropStyle;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DropStyle::ALL
+            .into_iter()
+            .find(|style| style.name() == s)
+            .ok_or(UnknownDropStyle)
+    }
+}
@@ -1,4 +1,6 @@
 use crate::gfx;
+use crate::input::Button;
+use crate::timer::Timer;
 use core::fmt::Debug;
 use embedded_graphics::{
     draw_target::DrawTarget,
@@ -18,13 +20,49 @@ const SIG_BOTTOM_PADDING: i32 = 0;
 
 const LINE_HEIGHT: i32 = 7;
 
+/// Ticks without input until the demo starts, about 10 seconds
+const ATTRACT_DELAY: u8 = 200;
+/// Ticks the credits and the settings hint are shown in turn, 2 seconds
+const HINT_PERIOD: u8 = 40;
+
 pub struct Intro {
     pub start: bool,
+    pub highscores: bool,
+    pub settings: bool,
+    idle: Timer,
+}
+
+impl Default for Intro {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Intro {
     pub const fn new() -> Self {
-        Intro { start: false }
+        Intro {
+            start: false,
+            highscores: false,
+            settings: false,
+            idle: Timer::new(ATTRACT_DELAY),
+        }
+    }
+
+    #[inline(always)]
+    pub fn tick(&mut self) {
+        self.idle.tick();
+    }
+
+    /// Returns true if nobody pressed a button for a while
+    #[inline(always)]
+    pub fn idle(&self) -> bool {
+        self.idle.is_due()
+    }
+
+    /// show high scores
+    #[inline(always)]
+    pub fn button_up(&mut self) {
+        self.highscores = true;
     }
 
     #[inline(always)]
@@ -37,6 +75,19 @@ impl Intro {
         self.button_center();
     }
 
+    #[inline(always)]
+    pub fn button_left(&mut self) {
+        self.idle.reset();
+    }
+
+    /// tapping left twice shows the settings
+    #[inline(always)]
+    pub fn double_tap(&mut self, button: Button) {
+        if button == Button::Left {
+            self.settings = true;
+        }
+    }
+
     #[inline(always)]
     pub fn button_center(&mut self) {
         self.start = true;
@@ -101,7 +152,18 @@ impl Intro {
     where
         <D as DrawTarget>::Error: Debug,
     {
-        self.text(TEXT_Y_POSITION, &["Designed and", "programmed by"], display);
+        let text = if self.showing_hint() {
+            ["Tap left twice", "for settings"]
+        } else {
+            ["Designed and", "programmed by"]
+        };
+        self.text(TEXT_Y_POSITION, &text, display);
+    }
+
+    /// The credits take turns with how to get to the settings
+    #[inline(always)]
+    pub fn showing_hint(&self) -> bool {
+        (self.idle.get() / HINT_PERIOD) % 2 == 1
     }
 
     #[inline(always)]
@@ -0,0 +1,139 @@
+//! Properties of the board that need to hold after every tick, used by the
+//! property tests and the fuzzer.
+
+use crate::ctx::{Context, Screen};
+use crate::game::{self, Game};
+use crate::gfx::tile::Tile;
+use crate::input::Button;
+use crate::random::Random;
+use rand_core::SeedableRng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Step {
+    Press(Button),
+    Release(Button),
+    Tick,
+}
+
+impl Step {
+    /// Decode one byte of fuzzer input, ticks are more likely than presses
+    pub const fn from_byte(byte: u8) -> Self {
+        match byte % 16 {
+            0 => Step::Press(Button::Up),
+            1 => Step::Press(Button::Down),
+            2 => Step::Press(Button::Left),
+            3 => Step::Press(Button::Right),
+            4 => Step::Press(Button::Center),
+            5 => Step::Release(Button::Up),
+            6 => Step::Release(Button::Down),
+            7 => Step::Release(Button::Left),
+            8 => Step::Release(Button::Right),
+            9 => Step::Release(Button::Center),
+            _ => Step::Tick,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// A tile of the current piece is outside of the playing field or on top of another tile
+    PieceOverlaps { lane: u32, row: i32 },
+    /// A piece was persisted into the lanes reserved for the blade
+    BladeLaneFilled { lane: usize, row: usize },
+    /// A wall tile disappeared from the board
+    WallRemoved { lane: usize, row: usize },
+    /// A wall tile below the blade was turned into a regular tile
+    WallWeakened { lane: usize, row: usize },
+}
+
+/// Check the state of a single game
+pub fn check_game(game: &Game) -> Result<(), Violation> {
+    // the piece is persisted before the level ends, it's expected to overlap
+    if game.in_transition() {
+        return Ok(());
+    }
+
+    for (lane, row) in game.piece_tiles() {
+        let outside = !(game::MIN_LANE..game::NUM_LANES).contains(&lane);
+        let overlaps = usize::try_from(row).is_ok_and(|y| {
+            game.lanes()
+                .get(lane as usize)
+                .and_then(|lane| lane.get(y))
+                .is_none_or(|tile| tile.is_some())
+        });
+        if outside || overlaps {
+            return Err(Violation::PieceOverlaps { lane, row });
+        }
+    }
+
+    Ok(())
+}
+
+/// Check the transition from one state of a game to the next
+pub fn check(before: &Game, after: &Game) -> Result<(), Violation> {
+    check_game(after)?;
+
+    // the blade can only break the row of the next obstacle
+    let below_blade = match before.next_obstacle() {
+        (Some(row), _) => row + 1,
+        (None, _) => 0,
+    };
+
+    for (lane, (before, after)) in before.lanes().iter().zip(after.lanes()).enumerate() {
+        for (row, (before, after)) in before.iter().zip(after).enumerate() {
+            match (before, after) {
+                (None, Some(_)) if lane < game::MIN_LANE as usize => {
+                    return Err(Violation::BladeLaneFilled { lane, row });
+                }
+                (Some(Tile { wall: true }), None) => {
+                    return Err(Violation::WallRemoved { lane, row });
+                }
+                (Some(Tile { wall: true }), Some(Tile { wall: false })) if row >= below_blade => {
+                    return Err(Violation::WallWeakened { lane, row });
+                }
+                _ => (),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Play the given steps, starting at a level, and check the invariants after each of them
+pub fn run<I: IntoIterator<Item = Step>>(level: u32, seed: u64, steps: I) -> Result<(), Violation> {
+    let mut ctx = Context::at_level(level);
+    let mut random = Random::seed_from_u64(seed);
+    // generated levels start once they're found
+    while !matches!(ctx.screen(), Screen::Game(_)) {
+        ctx.tick(&mut random);
+    }
+
+    for step in steps {
+        let before = match ctx.screen() {
+            Screen::Game(game) => Some(game.clone()),
+            _ => None,
+        };
+
+        let switch = match step {
+            Step::Press(button) => {
+                button.press(&mut ctx);
+                None
+            }
+            Step::Release(button) => {
+                button.release(&mut ctx);
+                None
+            }
+            Step::Tick => ctx.tick(&mut random),
+        };
+
+        // a level transition replaces the game
+        if switch.is_some() {
+            continue;
+        }
+        if let (Some(before), Screen::Game(after)) = (&before, ctx.screen()) {
+            check(before, after)?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+//! Level definitions, compiled from `levels.txt` by the build script.
+
+use crate::game;
+use crate::gravity::{self, Gravity, Ramp};
+use crate::pieces::Piece;
+
+mod generated {
+    use super::*;
+    include!(concat!(env!("OUT_DIR"), "/levels.rs"));
+}
+
+static_assertions::const_assert_eq!(generated::NUM_ROWS, game::NUM_ROWS);
+static_assertions::const_assert_eq!(
+    generated::BOARD_WIDTH,
+    (game::NUM_LANES - game::MIN_LANE) as usize
+);
+
+pub const BOARD_WIDTH: usize = generated::BOARD_WIDTH;
+
+pub struct Level {
+    /// Rows of regular obstacles, counted from the bottom
+    pub obstacles: &'static [u32],
+    /// Rows of tough obstacles, counted from the bottom
+    pub tough: &'static [u32],
+    /// Pre-filled tiles of the playfield, the last row is at the bottom
+    pub board: &'static [[bool; BOARD_WIDTH]],
+    /// Text shown at the start of the level
+    pub narrator: &'static [&'static str],
+    /// The first pieces of the level, random pieces follow
+    pub pieces: &'static [Piece],
+    /// Random pieces are only picked from these, all of them if it's empty
+    pub allowed: &'static [Piece],
+    /// How fast pieces fall, `None` uses `gravity::TABLE`
+    pub gravity: Option<Gravity>,
+    /// Speed up while the blade is stuck, `gravity::RAMP` unless the level sets it
+    pub ramp: Option<Ramp>,
+}
+
+impl Level {
+    /// Number of levels before the generated ones
+    pub const CAMPAIGN: u32 = generated::CAMPAIGN.len() as u32;
+
+    /// Levels after the campaign are `loop` levels, they're only played if
+    /// `generator::Search` didn't find a winnable level
+    pub const fn get(num: u32) -> &'static Level {
+        if num < Self::CAMPAIGN {
+            &generated::CAMPAIGN[num as usize]
+        } else {
+            &generated::ENDLESS[num as usize % generated::ENDLESS.len()]
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#![no_std]
+
+pub mod bot;
+pub mod ctx;
+pub mod demo;
+pub mod game;
+pub mod gameover;
+pub mod generator;
+pub mod gfx;
+pub mod gravity;
+pub mod highscores;
+pub mod initials;
+pub mod input;
+pub mod intro;
+#[cfg(feature = "invariants")]
+pub mod invariants;
+pub mod levels;
+pub mod narrator;
+pub mod paused;
+pub mod pieces;
+pub mod random;
+pub mod randomizer;
+pub mod replay;
+pub mod score;
+pub mod settings;
+pub mod storage;
+pub mod timer;
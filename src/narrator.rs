@@ -27,9 +27,9 @@ pub struct Narrator {
 }
 
 impl Narrator {
-    pub const fn level0() -> Self {
+    pub const fn new(text: &'static [&'static str]) -> Self {
         Narrator {
-            text: &["Oh no,", "it's stuck!", " ", "Can you help us?", "._."],
+            text,
             delay: Timer::new(3),
             scroll: Timer::infinite(),
         }
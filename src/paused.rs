@@ -0,0 +1,159 @@
+use crate::game::Game;
+use crate::gfx;
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    primitives::Rectangle,
+    text::{Baseline, Text},
+};
+
+const MENU_X_OFFSET: i32 = 6;
+const MENU_Y_OFFSET: i32 = 46;
+const MENU_WIDTH: u32 = 52;
+const MENU_HEIGHT: u32 = 36;
+const HEADLINE_Y_OFFSET: i32 = MENU_Y_OFFSET + 3;
+const CHOICES_Y_OFFSET: i32 = MENU_Y_OFFSET + 15;
+const LINE_HEIGHT: i32 = 6;
+const CURSOR_X_OFFSET: i32 = MENU_X_OFFSET + 3;
+const TEXT_X_OFFSET: i32 = MENU_X_OFFSET + 9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Choice {
+    Resume,
+    Restart,
+    Quit,
+}
+
+impl Choice {
+    pub const ALL: [Choice; 3] = [Choice::Resume, Choice::Restart, Choice::Quit];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Choice::Resume => "Resume",
+            Choice::Restart => "Restart",
+            Choice::Quit => "Quit",
+        }
+    }
+
+    fn next(&mut self) {
+        *self = Self::ALL[(*self as usize + 1) % Self::ALL.len()];
+    }
+
+    fn previous(&mut self) {
+        *self = Self::ALL[(*self as usize + Self::ALL.len() - 1) % Self::ALL.len()];
+    }
+}
+
+/// A game on hold, it's kept untouched until it's resumed
+#[derive(Clone)]
+pub struct Paused {
+    game: Game,
+    choice: Choice,
+    confirmed: bool,
+}
+
+impl Paused {
+    pub const fn new(game: Game) -> Self {
+        Self {
+            game,
+            choice: Choice::Resume,
+            confirmed: false,
+        }
+    }
+
+    #[inline]
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Get the game back to continue playing it
+    #[inline]
+    pub fn into_game(self) -> Game {
+        self.game
+    }
+
+    pub fn choice(&self) -> Option<Choice> {
+        self.confirmed.then_some(self.choice)
+    }
+
+    #[inline(always)]
+    pub fn button_up(&mut self) {
+        self.choice.previous();
+    }
+
+    #[inline(always)]
+    pub fn button_down(&mut self) {
+        self.choice.next();
+    }
+
+    #[inline(always)]
+    pub fn button_right(&mut self) {
+        self.button_center();
+    }
+
+    /// confirm selection
+    pub fn button_center(&mut self) {
+        self.confirmed = true;
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        self.game.render(display);
+
+        // dim the game by turning off every other pixel
+        let dimmed = (0..gfx::DISPLAY_HEIGHT).flat_map(|y| {
+            (y % 2..gfx::DISPLAY_WIDTH)
+                .step_by(2)
+                .map(move |x| Pixel(Point::new(x, y), BinaryColor::Off))
+        });
+        display.draw_iter(dimmed).unwrap();
+
+        // menu box
+        let menu = Rectangle::new(
+            Point::new(MENU_X_OFFSET, MENU_Y_OFFSET),
+            Size::new(MENU_WIDTH, MENU_HEIGHT),
+        );
+        menu.into_styled(gfx::BLACK).draw(display).unwrap();
+        menu.into_styled(gfx::WHITE_LINE).draw(display).unwrap();
+
+        let x = MENU_X_OFFSET
+            + gfx::text_align_center("Paused", MENU_WIDTH as i32, gfx::BIG_TEXT_STYLE.font);
+        Text::with_baseline(
+            "Paused",
+            Point::new(x, HEADLINE_Y_OFFSET),
+            gfx::BIG_TEXT_STYLE,
+            Baseline::Top,
+        )
+        .draw(display)
+        .unwrap();
+
+        // render options
+        for (num, choice) in Choice::ALL.into_iter().enumerate() {
+            let y = CHOICES_Y_OFFSET + num as i32 * LINE_HEIGHT;
+            Text::with_baseline(
+                choice.name(),
+                Point::new(TEXT_X_OFFSET, y),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            // render pointer
+            if choice == self.choice {
+                Text::with_baseline(
+                    ">",
+                    Point::new(CURSOR_X_OFFSET, y),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+            }
+        }
+    }
+}
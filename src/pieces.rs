@@ -1,15 +1,41 @@
 use crate::game::LANE_WIDTH;
+use crate::gfx;
 use crate::gfx::tile::Tile;
 use core::fmt::Debug;
-use embedded_graphics::{draw_target::DrawTarget, pixelcolor::BinaryColor, prelude::*};
+use embedded_graphics::{
+    draw_target::DrawTarget, pixelcolor::BinaryColor, prelude::*, primitives::Rectangle,
+};
 
 pub const GRID_WIDTH: u32 = 4;
 
 /// tiles[x][y]
 type Tiles = [[bool; 4]; GRID_WIDTH as usize];
 
-#[allow(dead_code)] // TODO
-#[derive(Debug, Clone, Copy, PartialEq)]
+/// Offsets as (lanes, rows) that are tried in order when rotating, rows grow downwards
+pub type Kicks = [(i32, i32); 5];
+
+// SRS kick tables with the rows flipped, indexed by the SRS state before turning. The
+// pieces spawn upside down compared to SRS, `Rotation::R0` is the SRS state 2.
+const NO_KICKS: Kicks = [(0, 0); 5];
+const JLSTZ_CLOCKWISE: [Kicks; 4] = [
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+];
+const JLSTZ_COUNTER_CLOCKWISE: [Kicks; 4] = [
+    [(0, 0), (1, 0), (1, -1), (0, 2), (1, 2)],
+    [(0, 0), (1, 0), (1, 1), (0, -2), (1, -2)],
+    [(0, 0), (-1, 0), (-1, -1), (0, 2), (-1, 2)],
+    [(0, 0), (-1, 0), (-1, 1), (0, -2), (-1, -2)],
+];
+// I, S and Z only lie in SRS state 2 and stand in state R, in either direction
+const I_LYING: Kicks = [(0, 0), (1, 0), (-2, 0), (1, 2), (-2, -1)];
+const I_STANDING: Kicks = [(0, 0), (-1, 0), (2, 0), (-1, -2), (2, 1)];
+const SZ_LYING: Kicks = JLSTZ_COUNTER_CLOCKWISE[2];
+const SZ_STANDING: Kicks = JLSTZ_CLOCKWISE[1];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Piece {
     O,
     I,
@@ -21,11 +47,35 @@ pub enum Piece {
 }
 
 impl Piece {
+    pub const ALL: [Piece; 7] = [
+        Self::O,
+        Self::I,
+        Self::J,
+        Self::L,
+        Self::T,
+        Self::S,
+        Self::Z,
+    ];
+
     #[inline(always)]
     pub const fn into_grid(self) -> Grid {
         Grid::new(self)
     }
 
+    /// Offsets to try when turning away from the given rotation
+    pub const fn kicks(&self, from: Rotation, direction: Direction) -> &'static Kicks {
+        let srs = (from as usize + 2) % 4;
+        match (self, from, direction) {
+            (Self::O, _, _) => &NO_KICKS,
+            (Self::I, Rotation::R0 | Rotation::R180, _) => &I_LYING,
+            (Self::I, Rotation::R90 | Rotation::R270, _) => &I_STANDING,
+            (Self::S | Self::Z, Rotation::R0 | Rotation::R180, _) => &SZ_LYING,
+            (Self::S | Self::Z, Rotation::R90 | Rotation::R270, _) => &SZ_STANDING,
+            (_, _, Direction::Clockwise) => &JLSTZ_CLOCKWISE[srs],
+            (_, _, Direction::CounterClockwise) => &JLSTZ_COUNTER_CLOCKWISE[srs],
+        }
+    }
+
     const fn tiles(&self, tiles: &mut Tiles, rotation: Rotation) {
         match (self, rotation) {
             (Self::O, _) => {
@@ -152,7 +202,7 @@ impl Piece {
     }
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Rotation {
     R0,
     R90,
@@ -161,12 +211,35 @@ pub enum Rotation {
 }
 
 impl Rotation {
-    pub fn rotate(&mut self) {
-        *self = match self {
-            Self::R0 => Self::R90,
-            Self::R90 => Self::R180,
-            Self::R180 => Self::R270,
-            Self::R270 => Self::R0,
+    pub const ALL: [Rotation; 4] = [Self::R0, Self::R90, Self::R180, Self::R270];
+
+    pub fn rotate(&mut self, direction: Direction) {
+        *self = match (*self, direction) {
+            (Self::R0, Direction::Clockwise) => Self::R90,
+            (Self::R90, Direction::Clockwise) => Self::R180,
+            (Self::R180, Direction::Clockwise) => Self::R270,
+            (Self::R270, Direction::Clockwise) => Self::R0,
+            (Self::R0, Direction::CounterClockwise) => Self::R270,
+            (Self::R90, Direction::CounterClockwise) => Self::R0,
+            (Self::R180, Direction::CounterClockwise) => Self::R90,
+            (Self::R270, Direction::CounterClockwise) => Self::R180,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Clockwise,
+    CounterClockwise,
+}
+
+impl Direction {
+    pub const ALL: [Direction; 2] = [Direction::Clockwise, Direction::CounterClockwise];
+
+    pub const fn opposite(&self) -> Direction {
+        match self {
+            Direction::Clockwise => Direction::CounterClockwise,
+            Direction::CounterClockwise => Direction::Clockwise,
         }
     }
 }
@@ -195,12 +268,23 @@ impl Grid {
         &self.tiles
     }
 
-    pub fn rotate(&mut self) {
+    #[inline]
+    pub fn rotation(&self) -> Rotation {
+        self.rotation
+    }
+
+    pub fn rotate(&mut self, direction: Direction) {
         self.tiles = Default::default();
-        self.rotation.rotate();
+        self.rotation.rotate(direction);
         self.piece.tiles(&mut self.tiles, self.rotation);
     }
 
+    /// Offsets to try when turning in this direction
+    #[inline]
+    pub fn kicks(&self, direction: Direction) -> &'static Kicks {
+        self.piece.kicks(self.rotation, direction)
+    }
+
     fn lowest_lane_point(lane: &[bool; 4]) -> Option<u8> {
         lane.iter()
             .enumerate()
@@ -254,4 +338,30 @@ impl Grid {
             }
         }
     }
+
+    /// Draw the piece with smaller, solid tiles, e.g. for the preview
+    pub fn render_small<D: DrawTarget<Color = BinaryColor>>(
+        &self,
+        display: &mut D,
+        point: Point,
+        size: u32,
+    ) where
+        <D as DrawTarget>::Error: Debug,
+    {
+        for (x, lane) in self.tiles.iter().enumerate() {
+            for (y, tile) in lane.iter().enumerate() {
+                if !tile {
+                    continue;
+                }
+
+                Rectangle::new(
+                    point + Point::new(size as i32 * x as i32, size as i32 * y as i32),
+                    Size::new(size, size),
+                )
+                .into_styled(gfx::WHITE)
+                .draw(display)
+                .unwrap();
+            }
+        }
+    }
 }
@@ -1,21 +1,31 @@
-use rand_core::RngCore;
+use rand_core::{RngCore, SeedableRng, impls};
+
+/// Number of samples taken from the hardware rng by the health check
+const HEALTH_CHECK_SAMPLES: usize = 16;
+/// Tolerated deviation from an equal number of set and unset bits
+const HEALTH_CHECK_MAX_BIAS: u32 = (HEALTH_CHECK_SAMPLES as u32 * u64::BITS) / 8;
 
 pub struct Random<R: RngCore> {
     ascon: ascon::State,
-    hwrng: R,
+    hwrng: Option<R>,
 }
 
 impl<R: RngCore> Random<R> {
     pub fn new(hwrng: R) -> Self {
         let ascon = ascon::State::default();
-        let mut random = Random { ascon, hwrng };
+        let mut random = Random {
+            ascon,
+            hwrng: Some(hwrng),
+        };
         random.absorb();
         random
     }
 
     pub fn absorb(&mut self) {
-        let input = self.hwrng.next_u64();
-        self.ascon[0] ^= input;
+        if let Some(hwrng) = &mut self.hwrng {
+            let input = hwrng.next_u64();
+            self.ascon[0] ^= input;
+        }
         self.ascon.permute_6();
     }
 
@@ -25,3 +35,105 @@ impl<R: RngCore> Random<R> {
         num
     }
 }
+
+impl<R: RngCore> RngCore for Random<R> {
+    fn next_u32(&mut self) -> u32 {
+        self.squeeze() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.squeeze()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+/// Stands in for the hardware rng if the sequence is only derived from a seed
+pub enum Seeded {}
+
+impl RngCore for Seeded {
+    fn next_u32(&mut self) -> u32 {
+        match *self {}
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        match *self {}
+    }
+
+    fn fill_bytes(&mut self, _dest: &mut [u8]) {
+        match *self {}
+    }
+
+    fn try_fill_bytes(&mut self, _dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        match *self {}
+    }
+}
+
+/// Only the seeded ones can be cloned, the hardware rng can't
+impl Clone for Random<Seeded> {
+    fn clone(&self) -> Self {
+        Random {
+            ascon: self.ascon.clone(),
+            hwrng: None,
+        }
+    }
+}
+
+/// Reproducible sequence without any hardware input, e.g. for replays
+impl SeedableRng for Random<Seeded> {
+    type Seed = [u8; 32];
+
+    fn from_seed(seed: Self::Seed) -> Self {
+        let mut words = [0; 5];
+        for (word, chunk) in words.iter_mut().zip(seed.chunks_exact(8)) {
+            let mut bytes = [0; 8];
+            bytes.copy_from_slice(chunk);
+            *word = u64::from_le_bytes(bytes);
+        }
+
+        let mut ascon = ascon::State::from(&words);
+        ascon.permute_12();
+        Random { ascon, hwrng: None }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthError {
+    /// The same value was read twice in a row
+    Repetition,
+    /// Significantly more bits were set than unset (or the other way around)
+    Bias(u32),
+}
+
+/// Sanity check the hardware rng is producing something that resembles entropy
+///
+/// This only detects a stuck or badly broken source, passing it doesn't mean
+/// the output is good.
+pub fn health_check<R: RngCore>(hwrng: &mut R) -> Result<(), HealthError> {
+    let mut previous = None;
+    let mut ones = 0;
+
+    for _ in 0..HEALTH_CHECK_SAMPLES {
+        let sample = hwrng.next_u64();
+        if previous == Some(sample) {
+            return Err(HealthError::Repetition);
+        }
+        previous = Some(sample);
+        ones += sample.count_ones();
+    }
+
+    let expected = (HEALTH_CHECK_SAMPLES as u32 * u64::BITS) / 2;
+    let bias = ones.abs_diff(expected);
+    if bias > HEALTH_CHECK_MAX_BIAS {
+        return Err(HealthError::Bias(bias));
+    }
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! Decides which pieces come next.
+//!
+//! The default is a 7-bag: all allowed pieces are put into a bag and drawn in
+//! random order, once the bag is empty it's refilled. Every piece shows up
+//! regularly and there are never long droughts. The history mode rolls each
+//! piece on its own but re-rolls pieces that were dealt recently, which is
+//! less predictable.
+//!
+//! Upcoming pieces are kept in a queue, so they can be shown as a preview.
+
+use crate::pieces::Piece;
+use crate::random::{Random, Seeded};
+use rand_core::{RngCore, SeedableRng};
+
+/// Most pieces that can be previewed
+pub const MAX_PREVIEW: usize = 3;
+/// Pieces remembered by the history mode
+const HISTORY: usize = 4;
+/// Rolls before the history mode accepts a recent piece anyway
+const HISTORY_ROLLS: u32 = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Bag,
+    History,
+}
+
+/// Set of pieces, e.g. the ones a level allows
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PieceSet(u8);
+
+impl PieceSet {
+    pub const EMPTY: Self = PieceSet(0);
+    pub const ALL: Self = Self::from_slice(&Piece::ALL);
+
+    pub const fn from_slice(pieces: &[Piece]) -> Self {
+        let mut set = Self::EMPTY;
+        let mut idx = 0;
+        while idx < pieces.len() {
+            set = set.with(pieces[idx]);
+            idx += 1;
+        }
+        set
+    }
+
+    #[inline]
+    pub const fn with(self, piece: Piece) -> Self {
+        PieceSet(self.0 | 1 << piece as u8)
+    }
+
+    #[inline]
+    pub const fn without(self, piece: Piece) -> Self {
+        PieceSet(self.0 & !(1 << piece as u8))
+    }
+
+    #[inline]
+    pub const fn contains(&self, piece: Piece) -> bool {
+        self.0 & 1 << piece as u8 != 0
+    }
+
+    #[inline]
+    pub const fn len(&self) -> u32 {
+        self.0.count_ones()
+    }
+
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = Piece> + '_ {
+        Piece::ALL.into_iter().filter(|piece| self.contains(*piece))
+    }
+
+    /// Pick one of the pieces, the set must not be empty
+    fn pick<R: RngCore>(&self, random: &mut R) -> Piece {
+        let idx = random.next_u32() % self.len();
+        self.iter().nth(idx as usize).unwrap_or(Piece::T)
+    }
+}
+
+#[derive(Clone)]
+pub struct Randomizer {
+    mode: Mode,
+    allowed: PieceSet,
+    /// Pieces set by the level, they're dealt before any random ones
+    sequence: &'static [Piece],
+    /// Pieces left in the current bag
+    bag: PieceSet,
+    /// Most recent piece first
+    history: [Option<Piece>; HISTORY],
+    queue: [Piece; MAX_PREVIEW],
+    queued: usize,
+    preview: usize,
+    /// Rolls the pieces instead of the random passed in, so they're known ahead
+    seeded: Option<Random<Seeded>>,
+}
+
+impl Default for Randomizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Randomizer {
+    pub const fn new() -> Self {
+        Randomizer {
+            mode: Mode::Bag,
+            allowed: PieceSet::ALL,
+            sequence: &[],
+            bag: PieceSet::EMPTY,
+            history: [None; HISTORY],
+            queue: [Piece::T; MAX_PREVIEW],
+            queued: 0,
+            preview: MAX_PREVIEW,
+            seeded: None,
+        }
+    }
+
+    pub const fn with_mode(mut self, mode: Mode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Only deal these pieces, an empty set allows all of them
+    pub const fn with_allowed(mut self, allowed: PieceSet) -> Self {
+        self.allowed = if allowed.is_empty() {
+            PieceSet::ALL
+        } else {
+            allowed
+        };
+        self
+    }
+
+    /// Deal these pieces first, random pieces follow
+    pub const fn with_sequence(mut self, sequence: &'static [Piece]) -> Self {
+        self.sequence = sequence;
+        self
+    }
+
+    /// Roll the pieces from this seed, whatever random is passed in
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seeded = Some(Random::seed_from_u64(seed));
+        self
+    }
+
+    /// Number of upcoming pieces to show, between 1 and `MAX_PREVIEW`
+    pub const fn with_preview(mut self, preview: usize) -> Self {
+        self.preview = if preview < 1 {
+            1
+        } else if preview > MAX_PREVIEW {
+            MAX_PREVIEW
+        } else {
+            preview
+        };
+        self
+    }
+
+    #[inline]
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    #[inline]
+    pub fn allowed(&self) -> PieceSet {
+        self.allowed
+    }
+
+    /// Make sure the preview is complete
+    pub fn fill<R: RngCore>(&mut self, random: &mut R) {
+        let mut seeded = self.seeded.take();
+        while self.queued < self.preview {
+            self.queue[self.queued] = match &mut seeded {
+                Some(seeded) => self.roll(seeded),
+                None => self.roll(random),
+            };
+            self.queued += 1;
+        }
+        self.seeded = seeded;
+    }
+
+    /// Take the next piece out of the queue
+    pub fn next<R: RngCore>(&mut self, random: &mut R) -> Piece {
+        self.fill(random);
+        let next = self.take();
+        self.fill(random);
+        next.unwrap_or(Piece::T)
+    }
+
+    /// Take the next piece without rolling new ones, `None` if the queue is empty
+    pub fn take(&mut self) -> Option<Piece> {
+        if let [next, rest @ ..] = self.sequence {
+            self.sequence = rest;
+            return Some(*next);
+        }
+
+        if self.queued == 0 {
+            return None;
+        }
+        let next = self.queue[0];
+        self.queue.rotate_left(1);
+        self.queued -= 1;
+        Some(next)
+    }
+
+    /// Upcoming pieces, next one first
+    pub fn preview(&self) -> impl Iterator<Item = Piece> + '_ {
+        self.sequence
+            .iter()
+            .chain(&self.queue[..self.queued])
+            .copied()
+            .take(self.preview)
+    }
+
+    fn roll<R: RngCore>(&mut self, random: &mut R) -> Piece {
+        let piece = match self.mode {
+            Mode::Bag => {
+                if self.bag.is_empty() {
+                    self.bag = self.allowed;
+                }
+                let piece = self.bag.pick(random);
+                self.bag = self.bag.without(piece);
+                piece
+            }
+            Mode::History => {
+                let mut piece = self.allowed.pick(random);
+                for _ in 1..HISTORY_ROLLS {
+                    if !self.history.contains(&Some(piece)) {
+                        break;
+                    }
+                    piece = self.allowed.pick(random);
+                }
+                piece
+            }
+        };
+        self.history.rotate_right(1);
+        self.history[0] = Some(piece);
+        piece
+    }
+}
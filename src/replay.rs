@@ -0,0 +1,221 @@
+//! Recorded sessions that can be played back deterministically.
+//!
+//! A replay is stored as text, the seed of the piece sequence and optionally
+//! the drop style, followed by one button event per line and the number of
+//! ticks the session lasted:
+//!
+//! ```text
+//! seed 1234
+//! drop modern
+//! 12 left
+//! 16 repeat left
+//! 17 release left
+//! 20 center
+//! 21 release center
+//! end 300
+//! ```
+//!
+//! The events are recorded after debouncing and auto-repeat, so the
+//! playback doesn't depend on how `Input` was configured.
+
+use crate::ctx::{Context, Screen};
+use crate::game::{Game, SwitchTo};
+use crate::input::{ButtonEvent, DropStyle};
+use crate::random::Random;
+use core::fmt;
+use rand_core::SeedableRng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The first entry needs to be the seed
+    MissingSeed,
+    /// The line couldn't be parsed
+    InvalidLine(usize),
+    /// Events need to be sorted by tick and happen before the end
+    OutOfOrder(usize),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingSeed => write!(f, "Replay doesn't start with a seed"),
+            ParseError::InvalidLine(line) => write!(f, "Invalid replay entry in line {line}"),
+            ParseError::OutOfOrder(line) => write!(f, "Replay entry out of order in line {line}"),
+        }
+    }
+}
+
+enum Entry {
+    Seed(u64),
+    Drop(DropStyle),
+    Event(ButtonEvent),
+    End(u32),
+}
+
+impl Entry {
+    fn parse(line: &str) -> Result<Option<Self>, ()> {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        if line.is_empty() {
+            return Ok(None);
+        }
+
+        let (key, value) = line.split_once(char::is_whitespace).ok_or(())?;
+        let value = value.trim();
+        let entry = match key {
+            "seed" => Entry::Seed(value.parse().map_err(|_| ())?),
+            "drop" => Entry::Drop(value.parse().map_err(|_| ())?),
+            "end" => Entry::End(value.parse().map_err(|_| ())?),
+            _ => Entry::Event(line.parse().map_err(|_| ())?),
+        };
+        Ok(Some(entry))
+    }
+}
+
+pub struct Replay<'a> {
+    seed: u64,
+    drop_style: DropStyle,
+    end: u32,
+    text: &'a str,
+}
+
+impl<'a> Replay<'a> {
+    pub fn parse(text: &'a str) -> Result<Self, ParseError> {
+        let mut seed = None;
+        let mut drop_style = None;
+        let mut end = None;
+        let mut next_tick = 0;
+        let mut pressed = false;
+
+        for (idx, line) in text.lines().enumerate() {
+            let line_num = idx + 1;
+            let entry = Entry::parse(line).map_err(|_| ParseError::InvalidLine(line_num))?;
+            match (entry, seed) {
+                (None, _) => (),
+                (Some(Entry::Seed(value)), None) => seed = Some(value),
+                (Some(_), None) => return Err(ParseError::MissingSeed),
+                (Some(Entry::Seed(_)), Some(_)) => return Err(ParseError::InvalidLine(line_num)),
+                (Some(_), Some(_)) if end.is_some() => {
+                    return Err(ParseError::OutOfOrder(line_num));
+                }
+                (Some(Entry::Drop(style)), Some(_)) => {
+                    // needs to be known before the game starts
+                    if pressed || drop_style.is_some() {
+                        return Err(ParseError::OutOfOrder(line_num));
+                    }
+                    drop_style = Some(style);
+                }
+                (Some(Entry::Event(event)), Some(_)) => {
+                    if event.tick < next_tick {
+                        return Err(ParseError::OutOfOrder(line_num));
+                    }
+                    next_tick = event.tick;
+                    pressed = true;
+                }
+                (Some(Entry::End(tick)), Some(_)) => {
+                    if tick <= next_tick {
+                        return Err(ParseError::OutOfOrder(line_num));
+                    }
+                    end = Some(tick);
+                }
+            }
+        }
+
+        let seed = seed.ok_or(ParseError::MissingSeed)?;
+        let end = end.unwrap_or(next_tick.saturating_add(1));
+        Ok(Replay {
+            seed,
+            drop_style: drop_style.unwrap_or_default(),
+            end,
+            text,
+        })
+    }
+
+    #[inline]
+    pub const fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    #[inline]
+    pub const fn drop_style(&self) -> DropStyle {
+        self.drop_style
+    }
+
+    /// Number of ticks the session lasted
+    #[inline]
+    pub const fn end(&self) -> u32 {
+        self.end
+    }
+
+    pub fn events(&self) -> impl Iterator<Item = ButtonEvent> + 'a {
+        self.text.lines().flat_map(|line| match Entry::parse(line) {
+            Ok(Some(Entry::Event(event))) => Some(event),
+            _ => None,
+        })
+    }
+
+    /// Play the recorded session from the very beginning
+    pub fn run(&self) -> Outcome {
+        let mut context = Context::new().with_drop_style(self.drop_style);
+        let mut random = Random::seed_from_u64(self.seed);
+        let mut events = self.events().peekable();
+        let mut game = None;
+        let mut switch = None;
+
+        for tick in 0..self.end {
+            while let Some(event) = events.next_if(|event| event.tick <= tick) {
+                context.handle(event);
+            }
+
+            // keep the board around, the game is gone after a transition
+            if let Screen::Game(current) = context.screen() {
+                game = Some(current.clone());
+            }
+
+            if let Some(target) = context.tick(&mut random) {
+                switch = Some(target);
+            }
+        }
+
+        if let Screen::Game(current) = context.screen() {
+            game = Some(current.clone());
+        }
+
+        Outcome {
+            context,
+            game,
+            switch,
+        }
+    }
+}
+
+pub struct Outcome {
+    /// The state the session ended in
+    pub context: Context,
+    /// The most recent game, as it was when the session ended or the level was left
+    pub game: Option<Game>,
+    /// The most recent level transition
+    pub switch: Option<SwitchTo>,
+}
+
+/// Write a session in the replay format while it's being played
+pub struct Recorder<W: fmt::Write> {
+    writer: W,
+}
+
+impl<W: fmt::Write> Recorder<W> {
+    pub fn new(mut writer: W, seed: u64, drop_style: DropStyle) -> Result<Self, fmt::Error> {
+        writeln!(writer, "seed {seed}")?;
+        writeln!(writer, "drop {drop_style}")?;
+        Ok(Recorder { writer })
+    }
+
+    pub fn record(&mut self, event: ButtonEvent) -> fmt::Result {
+        writeln!(self.writer, "{event}")
+    }
+
+    /// Mark the number of ticks the session lasted and return the writer
+    pub fn finish(mut self, end: u32) -> Result<W, fmt::Error> {
+        writeln!(self.writer, "end {end}")?;
+        Ok(self.writer)
+    }
+}
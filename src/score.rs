@@ -0,0 +1,67 @@
+//! Points collected during a run, kept apart by where they came from.
+
+/// Points per cleared row
+pub const ROW: u32 = 10;
+/// Bonus for clearing several rows at once, grows with every additional row
+pub const COMBO: u32 = 15;
+/// Points per tough obstacle broken by the blade
+pub const TOUGH: u32 = 25;
+/// Bonus for freeing the blade right away, it shrinks the longer the blade is stuck
+pub const BLADE: u32 = 50;
+/// Ticks after which freeing the blade isn't quick anymore
+pub const BLADE_WINDOW: u8 = 200;
+/// Points per row a piece was hard dropped
+pub const DROP: u32 = 1;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub rows: u32,
+    pub combo: u32,
+    pub blade: u32,
+    pub tough: u32,
+    pub drop: u32,
+}
+
+impl Score {
+    pub const fn new() -> Self {
+        Score {
+            rows: 0,
+            combo: 0,
+            blade: 0,
+            tough: 0,
+            drop: 0,
+        }
+    }
+
+    pub const fn total(&self) -> u32 {
+        self.rows
+            .saturating_add(self.combo)
+            .saturating_add(self.blade)
+            .saturating_add(self.tough)
+            .saturating_add(self.drop)
+    }
+
+    /// Rows that were cleared by the same piece
+    pub fn cleared_rows(&mut self, rows: u32) {
+        self.rows = self.rows.saturating_add(ROW.saturating_mul(rows));
+        // 2 rows: 1x, 3 rows: 3x, 4 rows: 6x
+        let combo = rows.saturating_sub(1).saturating_mul(rows) / 2;
+        self.combo = self.combo.saturating_add(COMBO.saturating_mul(combo));
+    }
+
+    /// The row the blade was stuck on was cleared after the given number of ticks
+    pub fn freed_blade(&mut self, ticks: u8) {
+        let remaining = u32::from(BLADE_WINDOW.saturating_sub(ticks));
+        self.blade = self
+            .blade
+            .saturating_add(BLADE * remaining / u32::from(BLADE_WINDOW));
+    }
+
+    pub fn broke_tough(&mut self) {
+        self.tough = self.tough.saturating_add(TOUGH);
+    }
+
+    pub fn hard_dropped(&mut self, rows: u32) {
+        self.drop = self.drop.saturating_add(DROP.saturating_mul(rows));
+    }
+}
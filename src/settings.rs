@@ -0,0 +1,338 @@
+//! Player settings, kept in flash, and the screen that changes them.
+
+use crate::gfx;
+use crate::input::{Button, DropStyle};
+use crate::pieces::Direction;
+use crate::storage::{self, Flash, Store};
+use core::fmt::Debug;
+use embedded_graphics::{
+    draw_target::DrawTarget,
+    pixelcolor::BinaryColor,
+    prelude::*,
+    text::{Alignment, Baseline, Text, TextStyleBuilder},
+};
+
+/// Key of the settings in the store
+pub const KEY: u8 = 2;
+/// Contrast goes from 1 to this
+pub const MAX_CONTRAST: u8 = 4;
+/// Milliseconds between frames the player can pick, slower frames slow the whole game down
+pub const FRAME_DELAYS: [u8; 5] = [30, 40, 50, 60, 70];
+
+const TITLE_Y_OFFSET: i32 = 8;
+const MENU_Y_OFFSET: i32 = 32;
+const LINE_HEIGHT: i32 = 9;
+const CURSOR_X_OFFSET: i32 = 2;
+const LABEL_X_OFFSET: i32 = 8;
+const VALUE_X_OFFSET: i32 = gfx::DISPLAY_WIDTH - 3;
+
+/// Which way up the display is mounted
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Orientation {
+    #[default]
+    Normal,
+    /// Turned around by 180 degrees, the buttons are swapped to match
+    Flipped,
+}
+
+impl Orientation {
+    pub const ALL: [Orientation; 2] = [Orientation::Normal, Orientation::Flipped];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Orientation::Normal => "normal",
+            Orientation::Flipped => "flipped",
+        }
+    }
+
+    /// The physical button that acts as the given one
+    pub const fn remap(&self, button: Button) -> Button {
+        match (self, button) {
+            (Orientation::Normal, button) => button,
+            (Orientation::Flipped, Button::Up) => Button::Down,
+            (Orientation::Flipped, Button::Down) => Button::Up,
+            (Orientation::Flipped, Button::Left) => Button::Right,
+            (Orientation::Flipped, Button::Right) => Button::Left,
+            (Orientation::Flipped, Button::Center) => Button::Center,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Settings {
+    pub orientation: Orientation,
+    /// From 1 to `MAX_CONTRAST`
+    pub contrast: u8,
+    /// Which way up turns the piece, a double-tap turns it the other way
+    pub up_rotation: Direction,
+    pub drop_style: DropStyle,
+    /// Milliseconds between frames, one of `FRAME_DELAYS`
+    pub frame_delay: u8,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Settings {
+    pub const fn new() -> Self {
+        Settings {
+            orientation: Orientation::Normal,
+            contrast: 2,
+            up_rotation: Direction::Clockwise,
+            drop_style: DropStyle::Modern,
+            frame_delay: 50,
+        }
+    }
+
+    /// Contrast as the display controller expects it
+    pub const fn display_contrast(&self) -> u8 {
+        self.contrast.saturating_mul(u8::MAX / MAX_CONTRAST)
+    }
+
+    /// Read the settings from the store, the defaults if there are none yet
+    pub fn load<F: Flash>(store: &Store<F>) -> Self {
+        store.get(KEY).map(Self::from_bytes).unwrap_or_default()
+    }
+
+    pub fn save<F: Flash>(&self, store: &mut Store<F>) -> Result<(), storage::Error<F::Error>> {
+        store.set(KEY, &self.to_bytes())?;
+        store.commit()
+    }
+
+    pub const fn to_bytes(&self) -> [u8; 5] {
+        [
+            self.orientation as u8,
+            self.contrast,
+            self.up_rotation as u8,
+            self.drop_style as u8,
+            self.frame_delay,
+        ]
+    }
+
+    /// Values that don't make sense, or are missing, keep their default
+    pub fn from_bytes(bytes: &[u8]) -> Self {
+        let mut settings = Self::new();
+        let byte = |idx: usize| bytes.get(idx).copied();
+        if let Some(orientation) = byte(0).and_then(|b| Orientation::ALL.get(b as usize)) {
+            settings.orientation = *orientation;
+        }
+        if let Some(contrast) = byte(1).filter(|b| (1..=MAX_CONTRAST).contains(b)) {
+            settings.contrast = contrast;
+        }
+        if let Some(up_rotation) = byte(2).and_then(|b| Direction::ALL.get(b as usize)) {
+            settings.up_rotation = *up_rotation;
+        }
+        if let Some(drop_style) = byte(3).and_then(|b| DropStyle::ALL.get(b as usize)) {
+            settings.drop_style = *drop_style;
+        }
+        if let Some(frame_delay) = byte(4).filter(|b| FRAME_DELAYS.contains(b)) {
+            settings.frame_delay = frame_delay;
+        }
+        settings
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Item {
+    Orientation,
+    Contrast,
+    Turn,
+    Drop,
+    Frame,
+    Back,
+}
+
+impl Item {
+    pub const ALL: [Item; 6] = [
+        Item::Orientation,
+        Item::Contrast,
+        Item::Turn,
+        Item::Drop,
+        Item::Frame,
+        Item::Back,
+    ];
+
+    pub const fn name(&self) -> &'static str {
+        match self {
+            Item::Orientation => "Screen",
+            Item::Contrast => "Contrast",
+            Item::Turn => "Up turns",
+            Item::Drop => "Drop",
+            Item::Frame => "Frame",
+            Item::Back => "Back",
+        }
+    }
+}
+
+/// The settings screen, changes are kept until the player goes back
+pub struct Menu {
+    settings: Settings,
+    item: Item,
+    exit: bool,
+}
+
+impl Menu {
+    pub const fn new(settings: Settings) -> Self {
+        Menu {
+            settings,
+            item: Item::Orientation,
+            exit: false,
+        }
+    }
+
+    #[inline]
+    pub fn settings(&self) -> &Settings {
+        &self.settings
+    }
+
+    #[inline]
+    pub fn done(&self) -> bool {
+        self.exit
+    }
+
+    pub fn button_up(&mut self) {
+        let idx = self.item as usize + Item::ALL.len() - 1;
+        self.item = Item::ALL[idx % Item::ALL.len()];
+    }
+
+    pub fn button_down(&mut self) {
+        let idx = self.item as usize + 1;
+        self.item = Item::ALL[idx % Item::ALL.len()];
+    }
+
+    #[inline(always)]
+    pub fn button_left(&mut self) {
+        self.change(false);
+    }
+
+    #[inline(always)]
+    pub fn button_right(&mut self) {
+        self.change(true);
+    }
+
+    /// change the selected setting, or leave
+    pub fn button_center(&mut self) {
+        if self.item == Item::Back {
+            self.exit = true;
+        } else {
+            self.change(true);
+        }
+    }
+
+    fn change(&mut self, up: bool) {
+        let settings = &mut self.settings;
+        match self.item {
+            Item::Orientation => {
+                settings.orientation = match settings.orientation {
+                    Orientation::Normal => Orientation::Flipped,
+                    Orientation::Flipped => Orientation::Normal,
+                };
+            }
+            Item::Contrast => {
+                settings.contrast = match (up, settings.contrast) {
+                    (true, MAX_CONTRAST) => 1,
+                    (true, contrast) => contrast.saturating_add(1),
+                    (false, 1) => MAX_CONTRAST,
+                    (false, contrast) => contrast.saturating_sub(1),
+                };
+            }
+            Item::Turn => settings.up_rotation = settings.up_rotation.opposite(),
+            Item::Drop => {
+                settings.drop_style = match settings.drop_style {
+                    DropStyle::Modern => DropStyle::Classic,
+                    DropStyle::Classic => DropStyle::Modern,
+                };
+            }
+            Item::Frame => {
+                let idx = FRAME_DELAYS
+                    .iter()
+                    .position(|&delay| delay == settings.frame_delay)
+                    .unwrap_or(0);
+                let idx = match up {
+                    true => idx + 1,
+                    false => idx + FRAME_DELAYS.len() - 1,
+                };
+                settings.frame_delay = FRAME_DELAYS[idx % FRAME_DELAYS.len()];
+            }
+            Item::Back => (),
+        }
+    }
+
+    fn value(&self, item: Item) -> &'static str {
+        let settings = &self.settings;
+        match item {
+            Item::Orientation => settings.orientation.name(),
+            Item::Contrast => ["1", "2", "3", "4"]
+                .get(settings.contrast.wrapping_sub(1) as usize)
+                .unwrap_or(&"?"),
+            Item::Turn => match settings.up_rotation {
+                Direction::Clockwise => "cw",
+                Direction::CounterClockwise => "ccw",
+            },
+            Item::Drop => settings.drop_style.name(),
+            Item::Frame => FRAME_DELAYS
+                .iter()
+                .position(|&delay| delay == settings.frame_delay)
+                .and_then(|idx| ["30ms", "40ms", "50ms", "60ms", "70ms"].get(idx))
+                .unwrap_or(&"?"),
+            Item::Back => "",
+        }
+    }
+
+    pub fn render<D: DrawTarget<Color = BinaryColor>>(&self, display: &mut D)
+    where
+        <D as DrawTarget>::Error: Debug,
+    {
+        let title = "Settings";
+        let x = gfx::text_align_center(title, gfx::DISPLAY_WIDTH, gfx::BIG_TEXT_STYLE.font);
+        Text::with_baseline(
+            title,
+            Point::new(x, TITLE_Y_OFFSET),
+            gfx::BIG_TEXT_STYLE,
+            Baseline::Top,
+        )
+        .draw(display)
+        .unwrap();
+
+        let right = TextStyleBuilder::new()
+            .alignment(Alignment::Right)
+            .baseline(Baseline::Top)
+            .build();
+
+        for (idx, item) in Item::ALL.into_iter().enumerate() {
+            let y = MENU_Y_OFFSET + idx as i32 * LINE_HEIGHT;
+            Text::with_baseline(
+                item.name(),
+                Point::new(LABEL_X_OFFSET, y),
+                gfx::TEXT_STYLE,
+                Baseline::Top,
+            )
+            .draw(display)
+            .unwrap();
+
+            Text::with_text_style(
+                self.value(item),
+                Point::new(VALUE_X_OFFSET, y),
+                gfx::TEXT_STYLE,
+                right,
+            )
+            .draw(display)
+            .unwrap();
+
+            // render pointer
+            if item == self.item {
+                Text::with_baseline(
+                    ">",
+                    Point::new(CURSOR_X_OFFSET, y),
+                    gfx::TEXT_STYLE,
+                    Baseline::Top,
+                )
+                .draw(display)
+                .unwrap();
+            }
+        }
+    }
+}
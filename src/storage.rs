@@ -0,0 +1,345 @@
+//! Persistent key/value store in the last few sectors of flash.
+//!
+//! The reserved sectors are split into two banks, A and B. Every commit goes
+//! to the bank that doesn't hold the current record, so losing power mid-write
+//! only ever damages the new copy and the previous one is still there on the
+//! next boot. Within a bank records are appended page by page and a sector is
+//! only erased once the bank wraps around, which spreads the wear over all
+//! pages.
+//!
+//! A record is one flash page: a header with a magic number, the format
+//! version, a sequence number and the length of the data, followed by the
+//! data and a crc32 of everything before it. The valid record with the highest
+//! sequence number wins.
+
+use core::fmt::Debug;
+
+pub const SECTOR_SIZE: u32 = 4096;
+pub const PAGE_SIZE: u32 = 256;
+/// Number of sectors reserved at the end of flash, keep in sync with `firmware/memory.x`
+pub const SECTORS: u32 = 4;
+/// Size of the reserved area in bytes
+pub const CAPACITY: u32 = SECTORS * SECTOR_SIZE;
+
+/// Bump this if the layout of the data changes and add a migration in `migrate`
+pub const FORMAT_VERSION: u16 = 1;
+
+const MAGIC: [u8; 2] = *b"cc";
+const HEADER_SIZE: usize = 10;
+const CRC_SIZE: usize = 4;
+/// Bytes available for keys, lengths and values
+pub const MAX_DATA: usize = PAGE_SIZE as usize - HEADER_SIZE - CRC_SIZE;
+
+const BANK_SIZE: u32 = CAPACITY / 2;
+const PAGES_PER_BANK: u32 = BANK_SIZE / PAGE_SIZE;
+const PAGES_PER_SECTOR: u32 = SECTOR_SIZE / PAGE_SIZE;
+
+static_assertions::const_assert!(SECTORS >= 2 && SECTORS.is_multiple_of(2));
+
+pub type Page = [u8; PAGE_SIZE as usize];
+
+/// Access to the reserved area, offsets are relative to its start
+pub trait Flash {
+    type Error: Debug;
+
+    fn read(&mut self, offset: u32, buf: &mut [u8]) -> Result<(), Self::Error>;
+
+    /// Set all bytes of the sector at the given offset to 0xff
+    fn erase(&mut self, offset: u32) -> Result<(), Self::Error>;
+
+    /// Write a page to an erased location, the offset is aligned to `PAGE_SIZE`
+    fn program(&mut self, offset: u32, page: &Page) -> Result<(), Self::Error>;
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error<E> {
+    /// Reading or writing the flash failed
+    Flash(E),
+    /// The value doesn't fit into the record anymore
+    Full,
+}
+
+/// Flash in RAM, used for tests and on the host
+///
+/// Like real flash, programming can only clear bits. Power loss can be
+/// simulated with `cut_power_after`, the operation that runs out of power is
+/// left half done.
+pub struct MemoryFlash {
+    data: [u8; CAPACITY as usize],
+    erases: [u32; SECTORS as usize],
+    power: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PowerLoss;
+
+impl Default for MemoryFlash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MemoryFlash {
+    pub const fn new() -> Self {
+        MemoryFlash {
+            data: [0xff; CAPACITY as usize],
+            erases: [0; SECTORS as usize],
+            power: None,
+        }
+    }
+
+    /// Fail the write that touches the byte after the given number of bytes
+    pub fn cut_power_after(&mut self, bytes: usize) {
+        self.power = Some(bytes);
+    }
+
+    /// Power is back, writes don't fail anymore
+    pub fn restore_power(&mut self) {
+        self.power = None;
+    }
+
+    /// How often the sector at the given index was erased
+    pub fn erase_count(&self, sector: usize) -> u32 {
+        self.erases[sector]
+    }
+
+    /// Write bytes one by one, until there's no power left
+    fn write<F: Fn(u8, u8) -> u8>(
+        &mut self,
+        offset: u32,
+        bytes: impl Iterator<Item = u8>,
+        f: F,
+    ) -> Result<(), PowerLoss> {
+        for (idx, byte) in bytes.enumerate() {
+            if let Some(power) = &mut self.power {
+                let Some(remaining) = power.checked_sub(1) else {
+                    return Err(PowerLoss);
+                };
+                *power = remaining;
+            }
+            let slot = &mut self.data[offset as usize + idx];
+            *slot = f(*slot, byte);
+        }
+        Ok(())
+    }
+}
+
+impl Flash for MemoryFlash {
+    type Error = PowerLoss;
+
+    fn read(&mut self, offset: u32, buf: &mut [u8]) -> Result<(), Self::Error> {
+        let offset = offset as usize;
+        buf.copy_from_slice(&self.data[offset..offset + buf.len()]);
+        Ok(())
+    }
+
+    fn erase(&mut self, offset: u32) -> Result<(), Self::Error> {
+        debug_assert!(offset.is_multiple_of(SECTOR_SIZE));
+        self.erases[(offset / SECTOR_SIZE) as usize] += 1;
+        let bytes = core::iter::repeat_n(0xff, SECTOR_SIZE as usize);
+        self.write(offset, bytes, |_, new| new)
+    }
+
+    fn program(&mut self, offset: u32, page: &Page) -> Result<(), Self::Error> {
+        debug_assert!(offset.is_multiple_of(PAGE_SIZE));
+        self.write(offset, page.iter().copied(), |old, new| old & new)
+    }
+}
+
+/// Standard crc32 (ieee), bit by bit to keep the code small
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in bytes {
+        crc ^= u32::from(*byte);
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+/// Bring data written by an older version up to date, returns false if that's not possible
+fn migrate(version: u16, _data: &mut [u8; MAX_DATA], _len: &mut usize) -> bool {
+    match version {
+        FORMAT_VERSION => true,
+        // no older versions yet
+        _ => false,
+    }
+}
+
+struct Record {
+    sequence: u32,
+    data: [u8; MAX_DATA],
+    len: usize,
+}
+
+impl Record {
+    fn parse(page: &Page) -> Option<Self> {
+        let (header, rest) = page.split_at(HEADER_SIZE);
+        if header[0..2] != MAGIC {
+            return None;
+        }
+        let version = u16::from_le_bytes([header[2], header[3]]);
+        let sequence = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+        let len = u16::from_le_bytes([header[8], header[9]]) as usize;
+        if version > FORMAT_VERSION || len > MAX_DATA {
+            return None;
+        }
+
+        let end = HEADER_SIZE + len;
+        let crc = u32::from_le_bytes(rest[len..len + CRC_SIZE].try_into().ok()?);
+        if crc32(&page[..end]) != crc {
+            return None;
+        }
+
+        let mut record = Record {
+            sequence,
+            data: [0; MAX_DATA],
+            len,
+        };
+        record.data[..len].copy_from_slice(&rest[..len]);
+        migrate(version, &mut record.data, &mut record.len).then_some(record)
+    }
+
+    fn write(&self, page: &mut Page) {
+        let end = HEADER_SIZE + self.len;
+        page.fill(0xff);
+        page[0..2].copy_from_slice(&MAGIC);
+        page[2..4].copy_from_slice(&FORMAT_VERSION.to_le_bytes());
+        page[4..8].copy_from_slice(&self.sequence.to_le_bytes());
+        page[8..10].copy_from_slice(&(self.len as u16).to_le_bytes());
+        page[HEADER_SIZE..end].copy_from_slice(&self.data[..self.len]);
+        let crc = crc32(&page[..end]);
+        page[end..end + CRC_SIZE].copy_from_slice(&crc.to_le_bytes());
+    }
+
+    /// Iterate over (key, value) pairs
+    fn entries(&self) -> impl Iterator<Item = (u8, &[u8])> {
+        let mut rest = &self.data[..self.len];
+        core::iter::from_fn(move || {
+            let [key, len, tail @ ..] = rest else {
+                return None;
+            };
+            let (value, tail) = tail.split_at_checked(*len as usize)?;
+            rest = tail;
+            Some((*key, value))
+        })
+    }
+}
+
+pub struct Store<F: Flash> {
+    flash: F,
+    record: Record,
+    /// Bank of the current record
+    bank: u32,
+    /// Next page to write to, per bank
+    cursor: [u32; 2],
+}
+
+impl<F: Flash> Store<F> {
+    /// Load the most recent valid record, an empty store if there's none
+    pub fn open(mut flash: F) -> Result<Self, Error<F::Error>> {
+        let mut record = Record {
+            sequence: 0,
+            data: [0; MAX_DATA],
+            len: 0,
+        };
+        let mut bank = 1;
+        let mut cursor = [0; 2];
+        let mut newest = [0; 2];
+
+        let mut page = [0; PAGE_SIZE as usize];
+        for b in 0..2 {
+            for p in 0..PAGES_PER_BANK {
+                flash
+                    .read(b * BANK_SIZE + p * PAGE_SIZE, &mut page)
+                    .map_err(Error::Flash)?;
+                let Some(found) = Record::parse(&page) else {
+                    continue;
+                };
+                if found.sequence > newest[b as usize] {
+                    newest[b as usize] = found.sequence;
+                    cursor[b as usize] = (p + 1) % PAGES_PER_BANK;
+                }
+                if found.sequence > record.sequence {
+                    bank = b;
+                    record = found;
+                }
+            }
+        }
+
+        Ok(Store {
+            flash,
+            record,
+            bank,
+            cursor,
+        })
+    }
+
+    pub fn get(&self, key: u8) -> Option<&[u8]> {
+        self.record
+            .entries()
+            .find(|(k, _)| *k == key)
+            .map(|(_, value)| value)
+    }
+
+    /// Change a value in memory, it's only persisted by `commit`
+    pub fn set(&mut self, key: u8, value: &[u8]) -> Result<(), Error<F::Error>> {
+        let mut next = Record {
+            sequence: self.record.sequence,
+            data: [0; MAX_DATA],
+            len: 0,
+        };
+        let others = self.record.entries().filter(|(k, _)| *k != key);
+        for (k, v) in others.chain([(key, value)]) {
+            let end = next.len + 2 + v.len();
+            if v.len() > u8::MAX as usize || end > MAX_DATA {
+                return Err(Error::Full);
+            }
+            next.data[next.len] = k;
+            next.data[next.len + 1] = v.len() as u8;
+            next.data[next.len + 2..end].copy_from_slice(v);
+            next.len = end;
+        }
+        self.record = next;
+        Ok(())
+    }
+
+    /// Write the current values to flash
+    pub fn commit(&mut self) -> Result<(), Error<F::Error>> {
+        let bank = 1 - self.bank;
+        let mut page = [0; PAGE_SIZE as usize];
+
+        // skip pages that were left behind by an interrupted write
+        let mut cursor = self.cursor[bank as usize];
+        for _ in 0..PAGES_PER_BANK {
+            if cursor.is_multiple_of(PAGES_PER_SECTOR) {
+                break;
+            }
+            let offset = bank * BANK_SIZE + cursor * PAGE_SIZE;
+            self.flash.read(offset, &mut page).map_err(Error::Flash)?;
+            if page.iter().all(|byte| *byte == 0xff) {
+                break;
+            }
+            cursor = (cursor + 1) % PAGES_PER_BANK;
+        }
+
+        let offset = bank * BANK_SIZE + cursor * PAGE_SIZE;
+        if cursor.is_multiple_of(PAGES_PER_SECTOR) {
+            self.flash.erase(offset).map_err(Error::Flash)?;
+        }
+
+        self.record.sequence = self.record.sequence.wrapping_add(1);
+        self.record.write(&mut page);
+        self.flash.program(offset, &page).map_err(Error::Flash)?;
+
+        self.bank = bank;
+        self.cursor[bank as usize] = (cursor + 1) % PAGES_PER_BANK;
+        Ok(())
+    }
+
+    pub fn into_inner(self) -> F {
+        self.flash
+    }
+}
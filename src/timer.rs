@@ -1,6 +1,6 @@
 #![allow(unused)]
 
-#[derive(Clone, Copy)]
+#[derive(Debug, Clone, Copy)]
 pub struct Timer {
     delay: u8,
     step: u8,
@@ -0,0 +1,52 @@
+use game_chop_chop::bot;
+use game_chop_chop::game::{Game, SwitchTo};
+use game_chop_chop::generator::Generated;
+use game_chop_chop::levels::Level;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+/// Let the bot play a level, pressing at most one button per tick
+fn play(level: u32, seed: u64) -> Option<SwitchTo> {
+    let generated = (level >= Level::CAMPAIGN)
+        .then(|| Generated::generate(level))
+        .flatten();
+    let mut game = match generated {
+        Some(generated) => generated.into_game(level),
+        None => Game::from_level(level),
+    };
+    let mut random = Random::seed_from_u64(seed);
+    for _ in 0..20_000 {
+        if let Some(button) = bot::next_button(&game) {
+            bot::press(&mut game, button);
+        }
+        game.tick(&mut random);
+        if let Some(switch) = game.transition() {
+            return Some(switch);
+        }
+    }
+    None
+}
+
+#[test]
+fn wins_first_levels() {
+    for level in 0..3 {
+        let won = (0..3)
+            .filter(|&seed| play(level, seed) == Some(SwitchTo::NextLevel(level + 1)))
+            .count();
+        // s and z pieces leave gaps, the bot loses the odd game
+        assert!(won >= 2, "bot only won level {level} {won} out of 3 times");
+    }
+}
+
+#[test]
+fn wins_most_levels() {
+    let mut won = 0;
+    for level in 0..10 {
+        for seed in 0..2 {
+            if play(level, seed) == Some(SwitchTo::NextLevel(level + 1)) {
+                won += 1;
+            }
+        }
+    }
+    assert!(won >= 14, "bot only won {won} out of 20 levels");
+}
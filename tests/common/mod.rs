@@ -0,0 +1,18 @@
+//! Helpers shared by the integration tests.
+
+use game_chop_chop::game::Game;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+/// Ticks until the current piece is placed, at most 1000
+pub fn ticks_to_place(mut game: Game) -> u32 {
+    let mut random = Random::seed_from_u64(1);
+    let placed = game.pieces_placed();
+    for ticks in 1..1000 {
+        game.tick(&mut random);
+        if game.pieces_placed() > placed {
+            return ticks;
+        }
+    }
+    1000
+}
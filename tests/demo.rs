@@ -0,0 +1,43 @@
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::input::Button;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+/// Ticks until the intro switches to the demo, about 10 seconds
+const ATTRACT_DELAY: usize = 200;
+
+#[test]
+fn idle_intro_starts_demo() {
+    let mut random = Random::seed_from_u64(0);
+    let mut ctx = Context::new();
+
+    for _ in 0..ATTRACT_DELAY - 1 {
+        ctx.tick(&mut random);
+    }
+    // buttons that don't leave the intro still count as input
+    Button::Left.press(&mut ctx);
+    for _ in 0..ATTRACT_DELAY - 1 {
+        ctx.tick(&mut random);
+    }
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Demo(_)));
+
+    // the bot is playing
+    for _ in 0..300 {
+        ctx.tick(&mut random);
+    }
+    let Screen::Demo(demo) = ctx.screen() else {
+        panic!("demo ended early");
+    };
+    assert!(!demo.game().narrating());
+    assert!(demo.game().pieces_placed() > 0);
+
+    // any button returns to the intro, without starting a game
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+}
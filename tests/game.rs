@@ -0,0 +1,192 @@
+mod common;
+
+use common::ticks_to_place;
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::input::Button;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+#[test]
+fn start_game_from_intro() {
+    let mut ctx = Context::new();
+    let mut random = Random::seed_from_u64(0);
+
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Game(_)));
+
+    // dropping pieces without ever moving them eventually ends the game
+    for _ in 0..10_000 {
+        // a short press of center drops the piece
+        Button::Center.press(&mut ctx);
+        Button::Center.release(&mut ctx);
+        ctx.tick(&mut random);
+        // hard drops score points, so the first game always makes it into the high scores
+        if matches!(ctx.screen(), Screen::Initials(_)) {
+            return;
+        }
+    }
+    panic!("game never ended");
+}
+
+#[test]
+fn hold_piece() {
+    use game_chop_chop::game::Game;
+
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::new(1);
+    // keep the blade from ending the level
+    game.add_obstacle_at_row(10);
+    let spawn_lane = game.lane();
+    game.tick(&mut random);
+
+    let first = game.piece().piece;
+    let next = game.preview().next();
+    game.button_left();
+    game.hold();
+    assert_eq!(game.held(), Some(first));
+    assert_eq!(Some(game.piece().piece), next);
+    assert_eq!(game.lane(), spawn_lane);
+
+    // only once per piece
+    let second = game.piece().piece;
+    game.hold();
+    assert_eq!(game.held(), Some(first));
+    assert_eq!(game.piece().piece, second);
+
+    // drop it, the next piece can be swapped with the held one
+    let placed = game.pieces_placed();
+    game.hard_drop();
+    for _ in 0..100 {
+        if game.pieces_placed() > placed {
+            break;
+        }
+        game.tick(&mut random);
+    }
+    assert_eq!(game.pieces_placed(), placed + 1);
+    let third = game.piece().piece;
+    game.hold();
+    assert_eq!(game.piece().piece, first);
+    assert_eq!(game.held(), Some(third));
+}
+
+#[test]
+fn modern_drop_style() {
+    use game_chop_chop::game::Game;
+    use game_chop_chop::input::DropStyle;
+
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::new(1).with_drop_style(DropStyle::Modern);
+    game.add_obstacle_at_row(10);
+    game.tick(&mut random);
+    let gravity = ticks_to_place(game.clone());
+    let mut hard = game.clone();
+    hard.hard_drop();
+    let hard_drop = ticks_to_place(hard);
+
+    // down drops faster while it's held
+    let mut soft = game.clone();
+    soft.button_down();
+    let soft_drop = ticks_to_place(soft.clone());
+    assert!(hard_drop < soft_drop && soft_drop < gravity);
+    soft.button_released(Button::Down);
+    assert_eq!(ticks_to_place(soft), gravity);
+
+    // tapping center drops the piece right away
+    let mut tapped = game.clone();
+    tapped.button_center();
+    tapped.button_released(Button::Center);
+    assert_eq!(ticks_to_place(tapped), hard_drop);
+
+    // holding center puts it on hold
+    let first = game.piece().piece;
+    game.button_center();
+    game.tick(&mut random);
+    assert_eq!(game.held(), None);
+    game.long_press(Button::Center);
+    assert_eq!(game.held(), Some(first));
+    let placed = game.pieces_placed();
+    game.button_released(Button::Center);
+    game.tick(&mut random);
+    assert_eq!(game.pieces_placed(), placed);
+}
+
+#[test]
+fn classic_drop_style() {
+    use game_chop_chop::game::Game;
+    use game_chop_chop::input::DropStyle;
+
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::new(1).with_drop_style(DropStyle::Classic);
+    game.add_obstacle_at_row(10);
+    game.tick(&mut random);
+
+    // down drops the piece right away
+    let mut hard = game.clone();
+    hard.hard_drop();
+    let mut dropped = game.clone();
+    dropped.button_down();
+    dropped.button_released(Button::Down);
+    let hard_drop = ticks_to_place(hard);
+    assert_eq!(ticks_to_place(dropped), hard_drop);
+    assert!(hard_drop < ticks_to_place(game.clone()));
+
+    let first = game.piece().piece;
+    game.button_center();
+    assert_eq!(game.held(), Some(first));
+}
+
+#[test]
+fn clear_rows_before_spawning() {
+    use game_chop_chop::bot::{self, Placement};
+    use game_chop_chop::game::Game;
+
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::new(1);
+    game.add_obstacle_at_row(10);
+    game.tick(&mut random);
+
+    // rotations and lane of each piece, stacking up to where pieces spawn
+    // until the last one completes the top row
+    let placements = [
+        (0, 4),
+        (0, 2),
+        (0, 3),
+        (0, 2),
+        (0, 4),
+        (0, 2),
+        (0, 4),
+        (0, 1),
+        (0, 4),
+        (0, 2),
+        (0, 4),
+        (0, 2),
+        (0, 3),
+        (1, 1),
+        (0, 4),
+        (2, 2),
+        (0, 4),
+        (0, 2),
+        (0, 5),
+    ];
+    for (rotations, lane) in placements {
+        let mut placement = Placement {
+            rotations,
+            lane,
+            score: 0,
+        };
+        let placed = game.pieces_placed();
+        while game.pieces_placed() == placed {
+            let button = placement.next_button(&game);
+            if button == Button::Up {
+                placement.rotations -= 1;
+            }
+            bot::press(&mut game, button);
+            game.tick(&mut random);
+        }
+    }
+
+    // the row made room for the next piece, the game goes on
+    assert!(!game.in_transition());
+    assert_eq!(game.to_string().lines().nth(1), Some(".#......"));
+}
@@ -0,0 +1,146 @@
+use game_chop_chop::bot;
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::generator::{self, Generated, Obstacle, Search};
+use game_chop_chop::input::{Button, Input};
+use game_chop_chop::levels::Level;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+use std::collections::BTreeSet;
+
+#[test]
+fn difficulty_rises() {
+    let mut random = Random::seed_from_u64(0);
+
+    let first = Generated::candidate(0, &mut random);
+    let obstacles = first.obstacles().collect::<Vec<_>>();
+    assert_eq!(obstacles.len(), 1);
+    assert!(obstacles[0].0 <= 4);
+    assert_eq!(obstacles[0].1, Obstacle::Normal);
+
+    let later = Generated::candidate(1000, &mut random);
+    let obstacles = later.obstacles().collect::<Vec<_>>();
+    assert_eq!(obstacles.len(), generator::MAX_OBSTACLES);
+    assert!(obstacles.iter().all(|(row, _)| *row <= generator::MAX_ROW));
+    let tough = obstacles
+        .iter()
+        .filter(|(_, obstacle)| *obstacle == Obstacle::Tough)
+        .count();
+    assert_eq!(tough, generator::MAX_TOUGH);
+}
+
+#[test]
+fn endless_campaign() {
+    let mut levels = BTreeSet::new();
+    for level in Level::CAMPAIGN..Level::CAMPAIGN + 10 {
+        let generated = Generated::generate(level).unwrap();
+        // the same level number always generates the same level
+        assert_eq!(Generated::generate(level), Some(generated));
+        levels.insert(generated.into_game(level).to_string());
+    }
+    // there are only a few ways to place the first single obstacles
+    assert!(levels.len() >= 5, "only {} different levels", levels.len());
+}
+
+#[test]
+fn later_levels_dont_repeat() {
+    let levels = (Level::CAMPAIGN + 50..Level::CAMPAIGN + 55)
+        .map(|level| Generated::generate(level).unwrap())
+        .collect::<Vec<_>>();
+    for (idx, level) in levels.iter().enumerate() {
+        assert!(!levels[idx + 1..].contains(level), "{level:?} repeats");
+    }
+}
+
+#[test]
+fn search_in_steps() {
+    let level = Level::CAMPAIGN + 20;
+    let mut search = Search::new(level);
+    let mut steps = 0;
+    while !search.done() {
+        assert_eq!(search.found(), None);
+        search.step();
+        steps += 1;
+    }
+    // one step per piece at most
+    assert!(steps > 20, "done after {steps} steps");
+    let generated = Generated::generate(level).unwrap();
+    assert_eq!(search.found(), Some(generated));
+    assert_eq!(
+        search.game().to_string(),
+        generated.into_game(level).to_string()
+    );
+}
+
+#[test]
+fn start_at_a_generated_level() {
+    let mut random = Random::seed_from_u64(1);
+    let level = Level::CAMPAIGN + 20;
+    let mut ctx = Context::at_level(level);
+    // the screen stays as it is until the level is found
+    let mut ticks = 0;
+    while !matches!(ctx.screen(), Screen::Game(_)) {
+        assert!(matches!(ctx.screen(), Screen::Intro(_)));
+        ctx.tick(&mut random);
+        ticks += 1;
+    }
+    assert!(ticks > 20, "started after {ticks} ticks");
+    let Screen::Game(game) = ctx.screen() else {
+        unreachable!();
+    };
+    let generated = Generated::generate(level).unwrap();
+    assert_eq!(game.to_string(), generated.into_game(level).to_string());
+
+    // restarting it from the pause menu doesn't search again
+    let mut input = Input::new();
+    input.update(ctx.ticks(), |button| {
+        matches!(button, Button::Left | Button::Right)
+    });
+    ctx.consume(input.queue());
+    assert!(matches!(ctx.screen(), Screen::Paused(_)));
+    Button::Down.press(&mut ctx);
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    let Screen::Game(game) = ctx.screen() else {
+        panic!("not in game");
+    };
+    assert_eq!(game.to_string(), generated.into_game(level).to_string());
+}
+
+#[test]
+fn pieces_are_dealt_from_the_level() {
+    let level = Level::CAMPAIGN + 3;
+    let mut one = Generated::generate(level).unwrap().into_game(level);
+    let mut other = one.clone();
+    let mut random = Random::seed_from_u64(1);
+    let mut random_other = Random::seed_from_u64(2);
+    for _ in 0..50 {
+        one.spawn_next_piece(&mut random);
+        other.spawn_next_piece(&mut random_other);
+        assert_eq!(one.piece().tiles(), other.piece().tiles());
+    }
+}
+
+#[test]
+fn campaign_continues_with_generated_levels() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(Level::CAMPAIGN - 1);
+    for _ in 0..20_000 {
+        // the transition stays on screen until the next level is found
+        let Screen::Game(game) = ctx.screen() else {
+            panic!("not in game");
+        };
+        if game.level() == Level::CAMPAIGN {
+            let generated = Generated::generate(Level::CAMPAIGN).unwrap();
+            assert_eq!(
+                game.to_string(),
+                generated.into_game(Level::CAMPAIGN).to_string()
+            );
+            return;
+        }
+        if let Some(button) = bot::next_button(game) {
+            button.press(&mut ctx);
+        }
+        ctx.tick(&mut random);
+    }
+    panic!("bot didn't win the last level of the campaign");
+}
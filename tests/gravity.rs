@@ -0,0 +1,145 @@
+mod common;
+
+use common::ticks_to_place;
+use game_chop_chop::game::Game;
+use game_chop_chop::gravity::{self, Gravity, LockDelay, Ramp};
+use game_chop_chop::levels::Level;
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+#[test]
+fn never_slows_down() {
+    for pair in gravity::TABLE.windows(2) {
+        let (a, b) = (pair[0], pair[1]);
+        // pixels per tick, compared without division
+        assert!(
+            b.pixels as u32 * a.delay as u32 >= a.pixels as u32 * b.delay as u32,
+            "{a:?} is faster than {b:?}"
+        );
+    }
+    let last = *gravity::TABLE.last().unwrap();
+    assert_eq!(Gravity::of_level(1000), last);
+}
+
+#[test]
+fn higher_levels_fall_faster() {
+    let ticks = |level| {
+        let mut game = Game::new(level).with_ramp(None);
+        game.add_obstacle_at_row(10);
+        ticks_to_place(game)
+    };
+    let slow = ticks(0);
+    let fast = ticks(10);
+    assert!(
+        fast < slow,
+        "level 10 took {fast} ticks, level 0 took {slow}"
+    );
+}
+
+#[test]
+fn set_by_the_level() {
+    // the level number's speed unless levels.txt sets one
+    for level in (0..Level::CAMPAIGN + 4).chain([u32::MAX]) {
+        let definition = Level::get(level);
+        assert_eq!(
+            Game::from_level(level).gravity(),
+            definition.gravity.unwrap_or(Gravity::of_level(level)),
+            "level {level}"
+        );
+    }
+}
+
+#[test]
+fn ramp() {
+    let ramp = Ramp {
+        after: 10,
+        every: 5,
+        max: 2,
+    };
+    assert_eq!(ramp.extra(0), 0);
+    assert_eq!(ramp.extra(9), 0);
+    assert_eq!(ramp.extra(10), 1);
+    assert_eq!(ramp.extra(15), 2);
+    assert_eq!(ramp.extra(255), 2);
+}
+
+#[test]
+fn speeds_up_while_stuck() {
+    let mut random = Random::seed_from_u64(1);
+    let base = Gravity::of_level(1);
+    let ramp = Ramp {
+        after: 50,
+        every: 10,
+        max: 2,
+    };
+
+    let mut game = Game::new(1).with_ramp(Some(ramp));
+    game.add_obstacle_at_row(10);
+    let mut fixed = game.clone().with_ramp(None);
+    for _ in 0..100 {
+        game.tick(&mut random);
+        fixed.tick(&mut random);
+    }
+    assert_eq!(game.gravity(), Gravity::new(base.delay, base.pixels + 2));
+    assert_eq!(fixed.gravity(), base);
+}
+
+/// A game with the piece resting on the floor, waiting to be placed
+fn landed(lock_delay: LockDelay) -> Game {
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::new(1).with_lock_delay(Some(lock_delay));
+    game.add_obstacle_at_row(10);
+    for _ in 0..1000 {
+        if game.locking() {
+            return game;
+        }
+        game.tick(&mut random);
+    }
+    panic!("piece never landed");
+}
+
+#[test]
+fn lock_delay() {
+    let mut game = Game::new(1);
+    game.add_obstacle_at_row(10);
+    let immediate = ticks_to_place(game.clone().with_lock_delay(None));
+    let delayed = ticks_to_place(game.clone().with_lock_delay(Some(LockDelay {
+        ticks: 5,
+        resets: 0,
+    })));
+    assert_eq!(delayed, immediate + 5);
+
+    // hard drops don't wait
+    let mut hard = game.clone();
+    hard.hard_drop();
+    let immediate = hard.clone().with_lock_delay(None);
+    assert_eq!(ticks_to_place(hard), ticks_to_place(immediate));
+}
+
+#[test]
+fn moving_resets_the_lock_delay() {
+    let lock_delay = LockDelay {
+        ticks: 3,
+        resets: 4,
+    };
+    let still = ticks_to_place(landed(lock_delay));
+
+    let mut random = Random::seed_from_u64(1);
+    let mut game = landed(lock_delay);
+    let mut moved = 0;
+    while game.pieces_placed() == 0 && moved < 100 {
+        if moved % 2 == 0 {
+            game.button_left();
+        } else {
+            game.button_right();
+        }
+        game.tick(&mut random);
+        moved += 1;
+    }
+    assert!(
+        moved > still,
+        "placed after {moved} ticks, {still} without moving"
+    );
+    // once the resets are used up, moving doesn't help anymore
+    assert!(moved <= still + lock_delay.resets as u32);
+}
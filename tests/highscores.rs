@@ -0,0 +1,112 @@
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::highscores::{Entry, HighScores, NUM_ENTRIES};
+use game_chop_chop::input::Button;
+use game_chop_chop::random::Random;
+use game_chop_chop::storage::{MemoryFlash, Store};
+use rand_core::SeedableRng;
+
+fn entry(initials: &[u8; 3], score: u32) -> Entry {
+    Entry {
+        initials: *initials,
+        score,
+    }
+}
+
+#[test]
+fn sorted_top_ten() {
+    let mut table = HighScores::new();
+    assert!(!table.qualifies(0));
+    assert_eq!(table.insert(entry(b"AAA", 3)), Some(0));
+    assert_eq!(table.insert(entry(b"BBB", 5)), Some(0));
+    // ties go after the existing entry
+    assert_eq!(table.insert(entry(b"CCC", 3)), Some(2));
+
+    for score in 10..20 {
+        table.insert(entry(b"DDD", score));
+    }
+    assert_eq!(table.entries().count(), NUM_ENTRIES);
+    assert!(!table.qualifies(10));
+    assert_eq!(table.insert(entry(b"EEE", 10)), None);
+    assert_eq!(table.insert(entry(b"FFF", 11)), Some(9));
+    let scores = table.entries().map(|entry| entry.score).collect::<Vec<_>>();
+    assert_eq!(scores, [19, 18, 17, 16, 15, 14, 13, 12, 11, 11]);
+}
+
+#[test]
+fn saved_in_flash() {
+    let mut store = Store::open(MemoryFlash::new()).unwrap();
+    assert_eq!(HighScores::load(&store), HighScores::new());
+
+    let mut table = HighScores::new();
+    table.insert(entry(b"KPC", 12));
+    table.insert(entry(b"ABC", 4));
+    table.save(&mut store).unwrap();
+
+    let store = Store::open(store.into_inner()).unwrap();
+    assert_eq!(HighScores::load(&store), table);
+
+    // garbage is dropped instead of shown
+    assert_eq!(
+        HighScores::from_bytes(b"ab\x00\x01\x00\x00\x00XYZ\x02\x00\x00\x00"),
+        HighScores::from_bytes(b"XYZ\x02\x00\x00\x00")
+    );
+}
+
+#[test]
+fn enter_initials_after_game_over() {
+    let mut random = Random::seed_from_u64(0);
+    let mut ctx = Context::at_level(2);
+
+    for _ in 0..10_000 {
+        // drop every piece right away
+        Button::Center.press(&mut ctx);
+        Button::Center.release(&mut ctx);
+        ctx.tick(&mut random);
+        if !matches!(ctx.screen(), Screen::Game(_)) {
+            break;
+        }
+    }
+    let Screen::Initials(initials) = ctx.screen() else {
+        panic!("no initials entry");
+    };
+    let score = initials.score().total();
+    assert!(score > 0);
+
+    // A -> Z, move right, A -> B, move right twice (stays on the last letter), A -> Y
+    Button::Down.press(&mut ctx);
+    Button::Right.press(&mut ctx);
+    Button::Up.press(&mut ctx);
+    Button::Right.press(&mut ctx);
+    Button::Right.press(&mut ctx);
+    Button::Down.press(&mut ctx);
+    Button::Down.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Initials(_)));
+    assert_eq!(ctx.take_unsaved_highscores(), None);
+
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Gameover(_)));
+
+    let mut expected = HighScores::new();
+    expected.insert(entry(b"ZBY", score));
+    assert_eq!(ctx.highscores(), &expected);
+    assert_eq!(ctx.take_unsaved_highscores(), Some(expected));
+    assert_eq!(ctx.take_unsaved_highscores(), None);
+}
+
+#[test]
+fn view_from_intro() {
+    let mut random = Random::seed_from_u64(0);
+    let mut table = HighScores::new();
+    table.insert(entry(b"KPC", 12));
+    let mut ctx = Context::new().with_highscores(table);
+
+    Button::Up.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::HighScores(_)));
+
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+}
@@ -0,0 +1,55 @@
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::Polyline;
+use game_chop_chop::game::Game;
+use game_chop_chop::gfx::blade::Blade;
+use game_chop_chop::gfx::hud::{self, Hud};
+use game_chop_chop::gfx::{self, Framebuffer};
+use game_chop_chop::pieces::Piece;
+
+fn pixels<F: FnOnce(&mut Framebuffer)>(f: F) -> Vec<Point> {
+    let mut frame = Framebuffer::new();
+    frame.clear(BinaryColor::Off).unwrap();
+    f(&mut frame);
+    (0..gfx::DISPLAY_HEIGHT)
+        .flat_map(|y| (0..gfx::DISPLAY_WIDTH).map(move |x| Point::new(x, y)))
+        .filter(|point| gfx::pixel(&frame, *point))
+        .collect()
+}
+
+#[test]
+fn stays_clear_of_the_blade() {
+    let hud = Hud {
+        level: 123,
+        score: 999_999,
+        obstacles: 21,
+        held: Some(Piece::L),
+        preview: [Some(Piece::I), Some(Piece::O), Some(Piece::Z)],
+    };
+    let mut blade = Blade::new();
+    while !blade.is_off_screen() {
+        let hud = pixels(|frame| hud.render(frame, &blade));
+        let blade_pixels = pixels(|frame| {
+            Polyline::new(&blade.points())
+                .into_styled(gfx::WHITE_LINE)
+                .draw(frame)
+                .unwrap();
+        });
+        assert!(
+            hud.iter().all(|point| !blade_pixels.contains(point)),
+            "HUD overlaps the blade at depth {}",
+            blade.depth()
+        );
+        assert!(hud.iter().all(|point| point.x < hud::WIDTH));
+        blade.move_towards(gfx::DISPLAY_HEIGHT * 2);
+    }
+}
+
+#[test]
+fn obstacles_remaining() {
+    let mut game = Game::new(1);
+    assert_eq!(game.obstacles_remaining(), 0);
+    game.add_obstacle_at_row(3);
+    game.add_tough_obstacle_at_row(7);
+    assert_eq!(game.obstacles_remaining(), 2);
+}
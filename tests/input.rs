@@ -0,0 +1,203 @@
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::input::{
+    AutoRepeat, Button, ButtonEvent, EventQueue, Gestures, Input, Kind, QUEUE_SIZE,
+};
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+/// Hold the buttons for the given ticks and collect all events
+fn events(
+    mut input: Input,
+    held: &[(Button, core::ops::Range<u32>)],
+    ticks: u32,
+) -> Vec<ButtonEvent> {
+    let mut events = Vec::new();
+    for tick in 0..ticks {
+        input.update(tick, |button| {
+            held.iter()
+                .any(|(held, range)| *held == button && range.contains(&tick))
+        });
+        while let Some(event) = input.pop() {
+            events.push(event);
+        }
+    }
+    events
+}
+
+/// Same as `events`, without any gestures
+fn presses(input: Input, held: &[(Button, core::ops::Range<u32>)], ticks: u32) -> Vec<ButtonEvent> {
+    events(input, held, ticks)
+        .into_iter()
+        .filter(|event| matches!(event.kind, Kind::Pressed | Kind::Repeated | Kind::Released))
+        .collect()
+}
+
+#[test]
+fn press_and_release() {
+    assert_eq!(
+        presses(Input::new(), &[(Button::Center, 2..3)], 5),
+        [
+            ButtonEvent::new(Button::Center, Kind::Pressed, 2),
+            ButtonEvent::new(Button::Center, Kind::Released, 3),
+        ]
+    );
+}
+
+#[test]
+fn debounce() {
+    // the contact bounces right after the press
+    let held = [(Button::Up, 0..1), (Button::Up, 2..3), (Button::Up, 4..9)];
+    assert_eq!(
+        presses(Input::new().with_debounce(3), &held, 10),
+        [
+            ButtonEvent::new(Button::Up, Kind::Pressed, 0),
+            ButtonEvent::new(Button::Up, Kind::Released, 3),
+            ButtonEvent::new(Button::Up, Kind::Pressed, 6),
+            ButtonEvent::new(Button::Up, Kind::Released, 9),
+        ]
+    );
+}
+
+#[test]
+fn auto_repeat() {
+    let input = Input::new().with_auto_repeat(Some(AutoRepeat { delay: 4, rate: 2 }));
+    let held = [(Button::Left, 0..9), (Button::Down, 0..9)];
+    assert_eq!(
+        presses(input, &held, 10),
+        [
+            ButtonEvent::new(Button::Down, Kind::Pressed, 0),
+            ButtonEvent::new(Button::Left, Kind::Pressed, 0),
+            ButtonEvent::new(Button::Left, Kind::Repeated, 4),
+            ButtonEvent::new(Button::Left, Kind::Repeated, 6),
+            ButtonEvent::new(Button::Left, Kind::Repeated, 8),
+            ButtonEvent::new(Button::Down, Kind::Released, 9),
+            ButtonEvent::new(Button::Left, Kind::Released, 9),
+        ]
+    );
+
+    let input = Input::new().with_auto_repeat(None);
+    assert_eq!(presses(input, &held, 10).len(), 4);
+}
+
+const GESTURES: Gestures = Gestures {
+    long_press: 3,
+    double_tap: 2,
+    chord: 1,
+};
+
+#[test]
+fn long_press() {
+    let input = Input::new().with_gestures(GESTURES);
+    assert_eq!(
+        events(input.clone(), &[(Button::Center, 0..6)], 8),
+        [
+            ButtonEvent::new(Button::Center, Kind::Pressed, 0),
+            ButtonEvent::new(Button::Center, Kind::LongPress, 3),
+            ButtonEvent::new(Button::Center, Kind::Released, 6),
+        ]
+    );
+    assert_eq!(events(input, &[(Button::Center, 0..3)], 8).len(), 2);
+}
+
+#[test]
+fn double_tap() {
+    let input = Input::new().with_gestures(GESTURES);
+    let held = [(Button::Up, 0..1), (Button::Up, 2..3), (Button::Up, 4..5)];
+    assert_eq!(
+        events(input.clone(), &held, 6),
+        [
+            ButtonEvent::new(Button::Up, Kind::Pressed, 0),
+            ButtonEvent::new(Button::Up, Kind::Released, 1),
+            ButtonEvent::new(Button::Up, Kind::Pressed, 2),
+            ButtonEvent::new(Button::Up, Kind::DoubleTap, 2),
+            ButtonEvent::new(Button::Up, Kind::Released, 3),
+            // a third tap starts over
+            ButtonEvent::new(Button::Up, Kind::Pressed, 4),
+            ButtonEvent::new(Button::Up, Kind::Released, 5),
+        ]
+    );
+
+    let held = [(Button::Up, 0..1), (Button::Up, 4..5)];
+    assert!(
+        !events(input, &held, 6)
+            .iter()
+            .any(|event| event.kind == Kind::DoubleTap)
+    );
+}
+
+#[test]
+fn chord() {
+    let input = Input::new().with_gestures(GESTURES);
+    let chords = |held: &[(Button, core::ops::Range<u32>)]| {
+        events(input.clone(), held, 6)
+            .into_iter()
+            .filter(|event| matches!(event.kind, Kind::Chord(_)))
+            .collect::<Vec<_>>()
+    };
+
+    let chord = ButtonEvent::new(Button::Left, Kind::Chord(Button::Right), 1);
+    assert_eq!(
+        chords(&[(Button::Right, 0..4), (Button::Left, 1..4)]),
+        [chord]
+    );
+    assert_eq!(
+        chords(&[(Button::Left, 0..4), (Button::Right, 1..4)]),
+        [chord]
+    );
+    assert_eq!(
+        chords(&[(Button::Left, 1..4), (Button::Right, 1..4)]),
+        [chord]
+    );
+    // pressed too far apart
+    assert_eq!(chords(&[(Button::Left, 0..4), (Button::Right, 2..4)]), []);
+}
+
+#[test]
+fn full_queue_drops_events() {
+    let mut queue = EventQueue::new();
+    for tick in 0..QUEUE_SIZE as u32 {
+        assert!(queue.push(ButtonEvent::new(Button::Up, Kind::Pressed, tick)));
+    }
+    assert!(!queue.push(ButtonEvent::new(Button::Up, Kind::Pressed, 99)));
+    assert_eq!(queue.len(), QUEUE_SIZE);
+    assert_eq!(queue.pop().map(|event| event.tick), Some(0));
+}
+
+#[test]
+fn holding_left_slides_the_piece() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(1);
+    let mut input = Input::new();
+
+    // skip the narrator, if there is one
+    for _ in 0..100 {
+        let Screen::Game(game) = ctx.screen() else {
+            panic!("not in game");
+        };
+        if !game.narrating() {
+            break;
+        }
+        Button::Down.press(&mut ctx);
+        Button::Down.release(&mut ctx);
+        ctx.tick(&mut random);
+    }
+
+    let Screen::Game(game) = ctx.screen() else {
+        panic!("not in game");
+    };
+    let mut leftmost = game.clone();
+    for _ in 0..10 {
+        leftmost.button_left();
+    }
+    assert!(leftmost.lane() < game.lane());
+
+    for _ in 0..20 {
+        input.update(ctx.ticks(), |button| button == Button::Left);
+        ctx.consume(input.queue());
+        ctx.tick(&mut random);
+    }
+    let Screen::Game(game) = ctx.screen() else {
+        panic!("not in game");
+    };
+    assert_eq!(game.lane(), leftmost.lane());
+}
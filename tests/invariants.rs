@@ -0,0 +1,31 @@
+use game_chop_chop::invariants::{self, Step};
+use proptest::prelude::*;
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn board_invariants(
+        level in 0u32..16,
+        seed in any::<u64>(),
+        input in proptest::collection::vec(any::<u8>(), 0..4000),
+    ) {
+        let steps = input.into_iter().map(Step::from_byte);
+        prop_assert_eq!(invariants::run(level, seed, steps), Ok(()));
+    }
+
+    /// Hammer the controls right after the piece spawned, when it's still above the screen
+    #[test]
+    fn spawn_area(
+        level in 0u32..16,
+        seed in any::<u64>(),
+        input in proptest::collection::vec(0u8..5, 0..64),
+    ) {
+        let mut steps = Vec::new();
+        for _ in 0..100 {
+            steps.extend(input.iter().copied().map(Step::from_byte));
+            steps.push(Step::Tick);
+        }
+        prop_assert_eq!(invariants::run(level, seed, steps), Ok(()));
+    }
+}
@@ -0,0 +1,21 @@
+use game_chop_chop::gravity;
+use game_chop_chop::levels::Level;
+
+#[test]
+fn campaign_then_loop() {
+    assert!(!Level::get(0).narrator.is_empty());
+    assert_eq!(Level::get(3).obstacles, [15, 4, 1]);
+
+    // after the campaign the fallback loop levels repeat by level number
+    assert_eq!(Level::CAMPAIGN, 6);
+    assert_eq!(Level::get(6).obstacles, [2, 6, 14]);
+    assert_eq!(Level::get(8).obstacles, [3, 4, 5, 6]);
+    assert_eq!(Level::get(9).tough, [4]);
+    assert_eq!(Level::get(u32::MAX).tough, [13]);
+
+    // no level sets its own speed yet, they all use the defaults
+    assert_eq!(Level::get(0).gravity, None);
+    assert_eq!(Level::get(0).ramp, gravity::RAMP);
+    assert_eq!(Level::get(u32::MAX).gravity, None);
+    assert_eq!(Level::get(u32::MAX).ramp, gravity::RAMP);
+}
@@ -0,0 +1,149 @@
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::game::Game;
+use game_chop_chop::gfx::{self, Framebuffer};
+use game_chop_chop::input::{Button, Input};
+use game_chop_chop::random::Random;
+use rand_core::SeedableRng;
+
+/// Read the buttons the way the firmware does and handle their events
+fn update(ctx: &mut Context, input: &mut Input, held: &[Button]) {
+    input.update(ctx.ticks(), |button| held.contains(&button));
+    ctx.consume(input.queue());
+}
+
+/// Press left and right together, then let go
+fn pause(ctx: &mut Context, input: &mut Input) {
+    update(ctx, input, &[Button::Left, Button::Right]);
+    update(ctx, input, &[]);
+}
+
+fn game(ctx: &Context) -> &Game {
+    match ctx.screen() {
+        Screen::Game(game) => game,
+        Screen::Paused(paused) => paused.game(),
+        _ => panic!("not in game"),
+    }
+}
+
+/// Render the game on its own, to compare two of them
+fn frame(game: &Game) -> Vec<bool> {
+    let mut frame = Framebuffer::new();
+    frame.clear(BinaryColor::Off).unwrap();
+    game.render(&mut frame);
+    (0..gfx::DISPLAY_HEIGHT)
+        .flat_map(|y| (0..gfx::DISPLAY_WIDTH).map(move |x| Point::new(x, y)))
+        .map(|point| gfx::pixel(&frame, point))
+        .collect()
+}
+
+#[test]
+fn pausing_freezes_the_game() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(1);
+    let mut input = Input::new();
+    for _ in 0..10 {
+        ctx.tick(&mut random);
+    }
+    let narrating = game(&ctx).narrating();
+    let before = game(&ctx).clone();
+
+    pause(&mut ctx, &mut input);
+    for _ in 0..100 {
+        Button::Up.press(&mut ctx);
+        Button::Up.release(&mut ctx);
+        ctx.tick(&mut random);
+    }
+    assert!(matches!(ctx.screen(), Screen::Paused(_)));
+    assert_eq!(game(&ctx).narrating(), narrating);
+    assert_eq!(game(&ctx).to_string(), before.to_string());
+
+    // the resumed game carries on exactly where it stopped
+    pause(&mut ctx, &mut input);
+    assert!(matches!(ctx.screen(), Screen::Game(_)));
+    let mut unpaused = before;
+    let mut random = Random::seed_from_u64(2);
+    let mut random_unpaused = Random::seed_from_u64(2);
+    for _ in 0..200 {
+        ctx.tick(&mut random);
+        unpaused.tick(&mut random_unpaused);
+        assert_eq!(frame(game(&ctx)), frame(&unpaused));
+    }
+}
+
+#[test]
+fn resume_from_the_menu() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(1);
+    let mut input = Input::new();
+    pause(&mut ctx, &mut input);
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Game(_)));
+}
+
+#[test]
+fn restart_level() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(3);
+    let mut input = Input::new();
+    for _ in 0..500 {
+        ctx.tick(&mut random);
+    }
+    pause(&mut ctx, &mut input);
+    Button::Down.press(&mut ctx);
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    let Screen::Game(game) = ctx.screen() else {
+        panic!("not in game");
+    };
+    assert_eq!(game.level(), 3);
+    assert_eq!(game.to_string(), Game::from_level(3).to_string());
+}
+
+#[test]
+fn quit_to_intro() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(1);
+    let mut input = Input::new();
+    pause(&mut ctx, &mut input);
+    Button::Up.press(&mut ctx);
+    Button::Right.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+}
+
+#[test]
+fn chord_presses_dont_reach_the_game() {
+    let mut random = Random::seed_from_u64(1);
+    let mut ctx = Context::at_level(1);
+    let mut input = Input::new();
+    // against the left wall, only the right press would move the piece
+    for _ in 0..10 {
+        Button::Left.press(&mut ctx);
+        Button::Left.release(&mut ctx);
+    }
+    ctx.tick(&mut random);
+    let before = game(&ctx).clone();
+
+    pause(&mut ctx, &mut input);
+    assert!(matches!(ctx.screen(), Screen::Paused(_)));
+    assert_eq!(game(&ctx).lane(), before.lane());
+    assert_eq!(game(&ctx).to_string(), before.to_string());
+    pause(&mut ctx, &mut input);
+    ctx.tick(&mut random);
+
+    // right goes down a tick after left
+    let before = game(&ctx).clone();
+    update(&mut ctx, &mut input, &[Button::Right]);
+    ctx.tick(&mut random);
+    let mut ticked = game(&ctx).clone();
+    update(&mut ctx, &mut input, &[Button::Left, Button::Right]);
+    assert!(matches!(ctx.screen(), Screen::Paused(_)));
+    assert_eq!(game(&ctx).lane(), before.lane());
+    assert_eq!(game(&ctx).to_string(), before.to_string());
+    // only the lane is taken back, the piece kept falling in the meantime
+    ticked.button_left();
+    assert_eq!(frame(game(&ctx)), frame(&ticked));
+}
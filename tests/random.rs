@@ -0,0 +1,56 @@
+use game_chop_chop::random::{self, HealthError, Random};
+use rand_core::{RngCore, SeedableRng, impls};
+
+/// Broken hardware rng that adds a fixed value on each read
+struct Broken {
+    value: u64,
+    step: u64,
+}
+
+impl RngCore for Broken {
+    fn next_u32(&mut self) -> u32 {
+        self.next_u64() as u32
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.value = self.value.wrapping_add(self.step);
+        self.value
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        impls::fill_bytes_via_next(self, dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[test]
+fn seeded_is_reproducible() {
+    let mut a = Random::seed_from_u64(1337);
+    let mut b = Random::seed_from_u64(1337);
+    let mut c = Random::seed_from_u64(1338);
+    for _ in 0..100 {
+        let num = a.squeeze();
+        assert_eq!(num, b.squeeze());
+        assert_ne!(num, c.squeeze());
+    }
+}
+
+#[test]
+fn health_check() {
+    assert_eq!(random::health_check(&mut Random::seed_from_u64(0)), Ok(()));
+    assert_eq!(
+        random::health_check(&mut Broken {
+            value: 0x5555_5555_5555_5555,
+            step: 0,
+        }),
+        Err(HealthError::Repetition)
+    );
+    assert!(matches!(
+        random::health_check(&mut Broken { value: 0, step: 1 }),
+        Err(HealthError::Bias(_))
+    ));
+}
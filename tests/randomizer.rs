@@ -0,0 +1,76 @@
+use game_chop_chop::pieces::Piece;
+use game_chop_chop::random::Random;
+use game_chop_chop::randomizer::{MAX_PREVIEW, Mode, PieceSet, Randomizer};
+use rand_core::SeedableRng;
+
+fn deal(randomizer: &mut Randomizer, seed: u64, count: usize) -> Vec<Piece> {
+    let mut random = Random::seed_from_u64(seed);
+    (0..count).map(|_| randomizer.next(&mut random)).collect()
+}
+
+#[test]
+fn every_bag_has_all_pieces() {
+    for seed in 0..10 {
+        let pieces = deal(&mut Randomizer::new(), seed, 7 * 20);
+        for bag in pieces.chunks_exact(7) {
+            assert_eq!(PieceSet::from_slice(bag), PieceSet::ALL, "seed {seed}");
+        }
+    }
+}
+
+#[test]
+fn history_avoids_repeats() {
+    let mut randomizer = Randomizer::new().with_mode(Mode::History);
+    let pieces = deal(&mut randomizer, 0, 1000);
+    assert_eq!(PieceSet::from_slice(&pieces), PieceSet::ALL);
+    let repeats = pieces.windows(2).filter(|w| w[0] == w[1]).count();
+    // without the history it would be about one in seven
+    assert!(repeats < 1000 / 20, "{repeats} repeats");
+}
+
+#[test]
+fn only_allowed_pieces() {
+    let allowed = PieceSet::from_slice(&[Piece::I, Piece::T, Piece::O]);
+    for mode in [Mode::Bag, Mode::History] {
+        let mut randomizer = Randomizer::new().with_mode(mode).with_allowed(allowed);
+        let pieces = deal(&mut randomizer, 1, 300);
+        assert_eq!(PieceSet::from_slice(&pieces), allowed, "{mode:?}");
+    }
+
+    // a single piece still works
+    let mut randomizer = Randomizer::new().with_allowed(PieceSet::EMPTY.with(Piece::I));
+    assert!(deal(&mut randomizer, 2, 20).iter().all(|p| *p == Piece::I));
+
+    // nothing allowed means everything is
+    let randomizer = Randomizer::new().with_allowed(PieceSet::EMPTY);
+    assert_eq!(randomizer.allowed(), PieceSet::ALL);
+}
+
+#[test]
+fn preview_shows_what_comes_next() {
+    let mut random = Random::seed_from_u64(3);
+    let mut randomizer = Randomizer::new().with_sequence(&[Piece::Z, Piece::O]);
+    randomizer.fill(&mut random);
+
+    let preview = randomizer.preview().collect::<Vec<_>>();
+    assert_eq!(preview.len(), MAX_PREVIEW);
+    assert_eq!(preview[..2], [Piece::Z, Piece::O]);
+
+    for _ in 0..20 {
+        let expected = randomizer.preview().collect::<Vec<_>>();
+        assert_eq!(randomizer.next(&mut random), expected[0]);
+        let preview = randomizer.preview().collect::<Vec<_>>();
+        assert_eq!(preview.len(), MAX_PREVIEW);
+        assert_eq!(preview[..MAX_PREVIEW - 1], expected[1..]);
+    }
+}
+
+#[test]
+fn preview_length() {
+    let mut random = Random::seed_from_u64(4);
+    for (preview, expected) in [(0, 1), (1, 1), (2, 2), (3, 3), (10, MAX_PREVIEW)] {
+        let mut randomizer = Randomizer::new().with_preview(preview);
+        randomizer.fill(&mut random);
+        assert_eq!(randomizer.preview().count(), expected);
+    }
+}
@@ -0,0 +1,134 @@
+use game_chop_chop::game::SwitchTo;
+use game_chop_chop::replay::Replay;
+
+fn run(text: &str) -> (String, Option<SwitchTo>) {
+    let replay = Replay::parse(text).unwrap();
+    let outcome = replay.run();
+    let board = outcome
+        .game
+        .expect("replay never started a game")
+        .to_string();
+    (board, outcome.switch)
+}
+
+#[test]
+fn hard_drops() {
+    let (board, switch) = run(include_str!("replays/hard-drops.txt"));
+    assert_eq!(switch, Some(SwitchTo::GameOver(0)));
+    assert_eq!(
+        board,
+        concat!(
+            ".#...oo.\n",
+            ".#..oo..\n",
+            ".#..ooo.\n",
+            ".#....o.\n",
+            ".#..ooo.\n",
+            ".#...o..\n",
+            ".#..ooo.\n",
+            ".#...o..\n",
+            ".#...oo.\n",
+            ".#...oo.\n",
+            ".#...oo.\n",
+            ".#..oo..\n",
+            ".#..oo..\n",
+            ".#...oo.\n",
+            "oo..oooo\n",
+            ".#..ooo.\n",
+            ".#....o.\n",
+            ".#..ooo.\n",
+            ".#..o...\n",
+            ".#..ooo.\n",
+            ".#...o..\n",
+        )
+    );
+}
+
+#[test]
+fn left_stack() {
+    let (board, switch) = run(include_str!("replays/left-stack.txt"));
+    assert_eq!(switch, Some(SwitchTo::GameOver(0)));
+    assert_eq!(
+        board,
+        concat!(
+            ".#......\n",
+            ".#.oo...\n",
+            ".#oo....\n",
+            ".#.oo...\n",
+            ".#.oo...\n",
+            ".#..o...\n",
+            ".#.oo...\n",
+            ".#.o....\n",
+            ".#.oo...\n",
+            ".#oo....\n",
+            ".#.o....\n",
+            ".#oo....\n",
+            ".#.o....\n",
+            ".#ooo...\n",
+            "ooo.o...\n",
+            ".#..o...\n",
+            ".#..o...\n",
+            ".#..o...\n",
+            ".#ooo.o.\n",
+            ".#.oo.o.\n",
+            ".#ooooo.\n",
+        )
+    );
+}
+
+#[test]
+fn reject_invalid_replays() {
+    use game_chop_chop::replay::ParseError;
+
+    assert_eq!(Replay::parse("1 up\n").err(), Some(ParseError::MissingSeed));
+    assert_eq!(
+        Replay::parse("seed 1\n1 jump\n").err(),
+        Some(ParseError::InvalidLine(2))
+    );
+    assert_eq!(
+        Replay::parse("seed 1\n5 up\n3 down\n").err(),
+        Some(ParseError::OutOfOrder(3))
+    );
+    assert_eq!(
+        Replay::parse("seed 1\n5 up\nend 5\n").err(),
+        Some(ParseError::OutOfOrder(3))
+    );
+    assert_eq!(
+        Replay::parse("seed 1\ndrop sideways\n").err(),
+        Some(ParseError::InvalidLine(2))
+    );
+    assert_eq!(
+        Replay::parse("seed 1\n1 hold center\n").err(),
+        Some(ParseError::InvalidLine(2))
+    );
+    assert_eq!(
+        Replay::parse("seed 1\n1 up\ndrop classic\n").err(),
+        Some(ParseError::OutOfOrder(3))
+    );
+}
+
+#[test]
+fn recorder_roundtrip() {
+    use game_chop_chop::input::{Button, ButtonEvent, DropStyle, Kind};
+    use game_chop_chop::replay::Recorder;
+
+    let events = [
+        ButtonEvent::new(Button::Center, Kind::Pressed, 0),
+        ButtonEvent::new(Button::Left, Kind::Pressed, 12),
+        ButtonEvent::new(Button::Left, Kind::Repeated, 16),
+        ButtonEvent::new(Button::Left, Kind::Released, 17),
+        ButtonEvent::new(Button::Center, Kind::LongPress, 18),
+        ButtonEvent::new(Button::Up, Kind::DoubleTap, 18),
+        ButtonEvent::new(Button::Left, Kind::Chord(Button::Right), 19),
+    ];
+    let mut recorder = Recorder::new(String::new(), 1337, DropStyle::Classic).unwrap();
+    for event in events {
+        recorder.record(event).unwrap();
+    }
+    let text = recorder.finish(20).unwrap();
+
+    let replay = Replay::parse(&text).unwrap();
+    assert_eq!(replay.seed(), 1337);
+    assert_eq!(replay.drop_style(), DropStyle::Classic);
+    assert_eq!(replay.end(), 20);
+    assert_eq!(replay.events().collect::<Vec<_>>(), events);
+}
@@ -0,0 +1,181 @@
+use game_chop_chop::bot;
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::game::Game;
+use game_chop_chop::input::{Button, Input};
+use game_chop_chop::invariants;
+use game_chop_chop::pieces::{Direction, Piece, Rotation};
+use game_chop_chop::random::Random;
+use game_chop_chop::settings::Settings;
+use rand_core::SeedableRng;
+
+const DIRECTIONS: [Direction; 2] = [Direction::Clockwise, Direction::CounterClockwise];
+
+/// A game with the piece turned into the given rotation, without kicks
+fn game(piece: Piece, rotation: Rotation) -> Game {
+    let mut game = Game::new(1).with_piece(piece);
+    while game.piece().rotation() != rotation {
+        assert!(game.rotate(Direction::Clockwise));
+    }
+    game
+}
+
+/// Move the piece as far as it goes
+fn push(game: &mut Game, button: Button) {
+    for _ in 0..10 {
+        match button {
+            Button::Left => game.button_left(),
+            _ => game.button_right(),
+        }
+    }
+}
+
+#[test]
+fn rotates_in_open_space() {
+    for piece in Piece::ALL {
+        for rotation in Rotation::ALL {
+            for direction in DIRECTIONS {
+                let mut game = game(piece, rotation);
+                let lane = game.lane();
+                assert!(game.rotate(direction));
+                // the first offset of every kick table is none at all
+                assert_eq!(game.lane(), lane, "{piece:?} {rotation:?} {direction:?}");
+            }
+        }
+    }
+}
+
+#[test]
+fn kicks_off_the_walls() {
+    // turns that don't fit next to a wall and how many lanes they're kicked,
+    // the same either way round, the others aren't kicked at all
+    const KICKED: [(Piece, Rotation, Button, i32); 14] = [
+        (Piece::I, Rotation::R90, Button::Left, 2),
+        (Piece::I, Rotation::R90, Button::Right, -1),
+        (Piece::I, Rotation::R270, Button::Left, 2),
+        (Piece::I, Rotation::R270, Button::Right, -1),
+        (Piece::J, Rotation::R90, Button::Right, -1),
+        (Piece::J, Rotation::R270, Button::Left, 1),
+        (Piece::L, Rotation::R90, Button::Right, -1),
+        (Piece::L, Rotation::R270, Button::Left, 1),
+        (Piece::T, Rotation::R90, Button::Right, -1),
+        (Piece::T, Rotation::R270, Button::Left, 1),
+        (Piece::S, Rotation::R90, Button::Left, 1),
+        (Piece::S, Rotation::R270, Button::Left, 1),
+        (Piece::Z, Rotation::R90, Button::Left, 1),
+        (Piece::Z, Rotation::R270, Button::Left, 1),
+    ];
+
+    for piece in Piece::ALL {
+        for rotation in Rotation::ALL {
+            for wall in [Button::Left, Button::Right] {
+                for direction in DIRECTIONS {
+                    let mut open = game(piece, rotation);
+                    let mut game = open.clone();
+                    push(&mut game, wall);
+                    let pushed = game.lane() as i32 - open.lane() as i32;
+                    assert!(open.rotate(direction));
+                    assert!(
+                        game.rotate(direction),
+                        "{piece:?} {rotation:?} at the {wall} wall, {direction:?}"
+                    );
+                    assert_eq!(invariants::check_game(&game), Ok(()));
+
+                    // compared to the same turn in open space, which isn't kicked
+                    let (lane, row) = game.piece_tiles().next().unwrap();
+                    let (open_lane, open_row) = open.piece_tiles().next().unwrap();
+                    let kick = (lane as i32 - open_lane as i32 - pushed, row - open_row);
+                    let expected = KICKED
+                        .iter()
+                        .find(|kicked| (kicked.0, kicked.1, kicked.2) == (piece, rotation, wall))
+                        .map_or(0, |kicked| kicked.3);
+                    assert_eq!(
+                        kick,
+                        (expected, 0),
+                        "{piece:?} {rotation:?} at the {wall} wall, {direction:?}"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn counter_clockwise_undoes_clockwise() {
+    for piece in Piece::ALL {
+        for rotation in Rotation::ALL {
+            let mut game = game(piece, rotation);
+            let tiles = *game.piece().tiles();
+            let lane = game.lane();
+            assert!(game.rotate(Direction::Clockwise));
+            assert!(game.rotate(Direction::CounterClockwise));
+            assert_eq!(game.piece().rotation(), rotation);
+            assert_eq!(*game.piece().tiles(), tiles);
+            assert_eq!(game.lane(), lane);
+        }
+    }
+}
+
+#[test]
+fn double_tap_turns_the_other_way() {
+    for (settings, direction) in [
+        (Settings::new(), Direction::CounterClockwise),
+        (
+            Settings {
+                up_rotation: Direction::CounterClockwise,
+                ..Settings::new()
+            },
+            Direction::Clockwise,
+        ),
+    ] {
+        let mut random = Random::seed_from_u64(1);
+        let mut ctx = Context::new().with_settings(settings);
+        let mut input = Input::new();
+        Button::Center.press(&mut ctx);
+        ctx.tick(&mut random);
+        let Screen::Game(game) = ctx.screen() else {
+            panic!("not in game");
+        };
+        let mut turned = game.clone();
+        assert!(turned.rotate(direction));
+
+        // tap, let go, tap again
+        for held in [&[Button::Up][..], &[], &[Button::Up], &[]] {
+            input.update(ctx.ticks(), |button| held.contains(&button));
+            ctx.consume(input.queue());
+            ctx.tick(&mut random);
+        }
+        let Screen::Game(game) = ctx.screen() else {
+            panic!("not in game");
+        };
+        assert_eq!(game.piece().rotation(), turned.piece().rotation());
+        assert_eq!(game.lane(), turned.lane());
+    }
+}
+
+#[test]
+fn double_tap_kicks_like_a_single_turn() {
+    let mut random = Random::seed_from_u64(1);
+    let mut game = Game::from_level(2);
+    // wherever the bot moves the pieces, against walls and the stack
+    for _ in 0..2000 {
+        let mut tapped = game.clone();
+        let mut turned = game.clone();
+        tapped.button_up();
+        tapped.button_up();
+        tapped.double_tap(Button::Up);
+        turned.rotate(Direction::CounterClockwise);
+        assert_eq!(
+            tapped.piece_tiles().collect::<Vec<_>>(),
+            turned.piece_tiles().collect::<Vec<_>>(),
+            "{game}"
+        );
+
+        if let Some(button) = bot::next_button(&game) {
+            bot::press(&mut game, button);
+        }
+        game.tick(&mut random);
+        if game.in_transition() {
+            break;
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use game_chop_chop::bot;
+use game_chop_chop::game::{Game, SwitchTo};
+use game_chop_chop::random::Random;
+use game_chop_chop::score::{self, Score};
+use rand_core::SeedableRng;
+
+#[test]
+fn points() {
+    let mut score = Score::new();
+    score.cleared_rows(1);
+    assert_eq!((score.rows, score.combo), (score::ROW, 0));
+    score.cleared_rows(4);
+    assert_eq!(
+        (score.rows, score.combo),
+        (5 * score::ROW, 6 * score::COMBO)
+    );
+
+    score.freed_blade(0);
+    assert_eq!(score.blade, score::BLADE);
+    score.freed_blade(score::BLADE_WINDOW / 2);
+    assert_eq!(score.blade, score::BLADE + score::BLADE / 2);
+    score.freed_blade(u8::MAX);
+    assert_eq!(score.blade, score::BLADE + score::BLADE / 2);
+
+    score.broke_tough();
+    score.hard_dropped(12);
+    assert_eq!(score.tough, score::TOUGH);
+    assert_eq!(score.drop, 12 * score::DROP);
+
+    assert_eq!(
+        score.total(),
+        score.rows + score.combo + score.blade + score.tough + score.drop
+    );
+}
+
+/// Let the bot play a level and return the finished game
+fn play(level: u32, seed: u64) -> Game {
+    let mut game = Game::from_level(level);
+    let mut random = Random::seed_from_u64(seed);
+    while game.transition().is_none() {
+        if let Some(button) = bot::next_button(&game) {
+            bot::press(&mut game, button);
+        }
+        game.tick(&mut random);
+    }
+    game
+}
+
+#[test]
+fn scored_while_playing() {
+    // one tough obstacle and a regular one, the bot doesn't win every game
+    let game = (0..10)
+        .map(|seed| play(5, seed))
+        .find(|game| game.transition() == Some(SwitchTo::NextLevel(6)))
+        .expect("bot never won level 5");
+
+    let score = game.score();
+    assert_eq!(score.tough, score::TOUGH);
+    // the blade was freed twice
+    assert!(score.rows >= 2 * score::ROW, "{score:?}");
+    assert!(score.blade > 0, "{score:?}");
+    // the bot hard drops every piece
+    assert!(score.drop >= game.pieces_placed(), "{score:?}");
+
+    // points are kept on the next level
+    let next = Game::from_level(6).with_score(*score);
+    assert_eq!(next.score(), score);
+}
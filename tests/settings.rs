@@ -0,0 +1,125 @@
+use game_chop_chop::ctx::{Context, Screen};
+use game_chop_chop::game::Game;
+use game_chop_chop::highscores::{Entry, HighScores};
+use game_chop_chop::input::{Button, ButtonEvent, DropStyle, Kind};
+use game_chop_chop::pieces::Direction;
+use game_chop_chop::random::Random;
+use game_chop_chop::settings::{Orientation, Settings};
+use game_chop_chop::storage::{MemoryFlash, Store};
+use rand_core::SeedableRng;
+
+fn open_settings(ctx: &mut Context, random: &mut Random<impl rand_core::RngCore>) {
+    ctx.handle(ButtonEvent::new(Button::Left, Kind::DoubleTap, ctx.ticks()));
+    ctx.tick(random);
+    assert!(matches!(ctx.screen(), Screen::Settings(_)));
+}
+
+#[test]
+fn saved_in_flash() {
+    let mut store = Store::open(MemoryFlash::new()).unwrap();
+    assert_eq!(Settings::load(&store), Settings::new());
+
+    let mut highscores = HighScores::new();
+    highscores.insert(Entry {
+        initials: *b"KPC",
+        score: 12,
+    });
+    highscores.save(&mut store).unwrap();
+
+    let settings = Settings {
+        orientation: Orientation::Flipped,
+        contrast: 4,
+        up_rotation: Direction::CounterClockwise,
+        drop_style: DropStyle::Classic,
+        frame_delay: 70,
+    };
+    settings.save(&mut store).unwrap();
+
+    let store = Store::open(store.into_inner()).unwrap();
+    assert_eq!(Settings::load(&store), settings);
+    assert_eq!(HighScores::load(&store), highscores);
+
+    // garbage keeps the defaults, so do values that were added later,
+    // the last byte used to be the sound setting
+    assert_eq!(
+        Settings::from_bytes(&[7, 0, 1, 9, 1]),
+        Settings {
+            up_rotation: Direction::CounterClockwise,
+            ..Settings::new()
+        }
+    );
+}
+
+#[test]
+fn flipped_buttons() {
+    for button in Button::ALL {
+        assert_eq!(Orientation::Normal.remap(button), button);
+        let flipped = Orientation::Flipped.remap(button);
+        assert_eq!(Orientation::Flipped.remap(flipped), button);
+    }
+    assert_eq!(Orientation::Flipped.remap(Button::Up), Button::Down);
+    assert_eq!(Orientation::Flipped.remap(Button::Left), Button::Right);
+}
+
+#[test]
+fn change_settings_from_the_intro() {
+    let mut random = Random::seed_from_u64(0);
+    let mut ctx = Context::new();
+    open_settings(&mut ctx, &mut random);
+
+    // down to the drop style, switch it and go back
+    for _ in 0..3 {
+        Button::Down.press(&mut ctx);
+    }
+    Button::Right.press(&mut ctx);
+    Button::Up.press(&mut ctx);
+    Button::Center.press(&mut ctx);
+    // back is at the bottom
+    for _ in 0..3 {
+        Button::Down.press(&mut ctx);
+    }
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+
+    let expected = Settings {
+        up_rotation: Direction::CounterClockwise,
+        drop_style: DropStyle::Classic,
+        ..Settings::new()
+    };
+    assert_eq!(ctx.take_unsaved_settings(), Some(expected));
+    assert_eq!(ctx.take_unsaved_settings(), None);
+
+    // the next game uses them
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    let Screen::Game(game) = ctx.screen() else {
+        panic!("not in game");
+    };
+    assert_eq!(game.drop_style(), DropStyle::Classic);
+
+    // leaving without changes doesn't need a save
+    let mut ctx = Context::new();
+    open_settings(&mut ctx, &mut random);
+    Button::Up.press(&mut ctx);
+    Button::Center.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert!(matches!(ctx.screen(), Screen::Intro(_)));
+    assert_eq!(ctx.take_unsaved_settings(), None);
+}
+
+#[test]
+fn up_turns_either_way() {
+    let game = Game::new(1);
+    for direction in Direction::ALL {
+        let settings = Settings {
+            up_rotation: direction,
+            ..Settings::new()
+        };
+        let mut up = game.clone().with_up_rotation(settings.up_rotation);
+        up.button_up();
+        let mut turned = game.clone();
+        assert!(turned.rotate(direction));
+        assert_eq!(up.piece().rotation(), turned.piece().rotation());
+    }
+}
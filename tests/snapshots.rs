@@ -0,0 +1,287 @@
+//! Render every screen and compare it to the reference images in `tests/snapshots/`.
+//!
+//! The references are plain pbm files, to update them after an intentional
+//! change run the tests with `UPDATE_SNAPSHOTS=1` and review the diff.
+
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use game_chop_chop::ctx::Context;
+use game_chop_chop::demo::Demo;
+use game_chop_chop::game::Game;
+use game_chop_chop::gameover::Gameover;
+use game_chop_chop::gfx::{self, Framebuffer};
+use game_chop_chop::highscores::{Entry, HighScores};
+use game_chop_chop::initials::Initials;
+use game_chop_chop::input::{Button, ButtonEvent, Gestures, Input, Kind};
+use game_chop_chop::intro::Intro;
+use game_chop_chop::random::{Random, Seeded};
+use game_chop_chop::score::Score;
+use game_chop_chop::settings::{Menu, Settings};
+use rand_core::SeedableRng;
+use std::env;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+fn render<F: FnOnce(&mut Framebuffer)>(f: F) -> Vec<Vec<bool>> {
+    let mut frame = Framebuffer::new();
+    frame.clear(BinaryColor::Off).unwrap();
+    f(&mut frame);
+
+    (0..gfx::DISPLAY_HEIGHT)
+        .map(|y| {
+            (0..gfx::DISPLAY_WIDTH)
+                .map(|x| gfx::pixel(&frame, Point::new(x, y)))
+                .collect()
+        })
+        .collect()
+}
+
+fn to_pbm(image: &[Vec<bool>]) -> String {
+    let mut pbm = format!("P1\n{} {}\n", gfx::DISPLAY_WIDTH, gfx::DISPLAY_HEIGHT);
+    for row in image {
+        pbm.extend(row.iter().map(|on| if *on { '1' } else { '0' }));
+        pbm.push('\n');
+    }
+    pbm
+}
+
+fn from_pbm(pbm: &str) -> Vec<Vec<bool>> {
+    let mut lines = pbm.lines().filter(|line| !line.starts_with('#'));
+    assert_eq!(
+        lines.next(),
+        Some("P1"),
+        "Reference is not a plain pbm file"
+    );
+    assert_eq!(
+        lines.next(),
+        Some(format!("{} {}", gfx::DISPLAY_WIDTH, gfx::DISPLAY_HEIGHT).as_str()),
+        "Reference has the wrong dimensions"
+    );
+    lines
+        .map(|line| line.chars().map(|c| c == '1').collect())
+        .collect()
+}
+
+/// Draw both images on top of each other, `+` is only in the rendered
+/// image and `-` is only in the reference
+fn diff(actual: &[Vec<bool>], expected: &[Vec<bool>]) -> Option<String> {
+    let mut out = String::new();
+    let mut changed = 0;
+    for y in 0..gfx::DISPLAY_HEIGHT as usize {
+        let mut line = String::new();
+        for x in 0..gfx::DISPLAY_WIDTH as usize {
+            let actual = actual.get(y).and_then(|row| row.get(x)).copied();
+            let expected = expected.get(y).and_then(|row| row.get(x)).copied();
+            line.push(match (actual, expected) {
+                (Some(true), Some(true)) => '#',
+                (Some(false), Some(false)) => '.',
+                (Some(true), _) => '+',
+                (_, _) => '-',
+            });
+        }
+        changed += line.matches(['+', '-']).count();
+        writeln!(out, "{y:3} {line}").unwrap();
+    }
+    (changed > 0).then(|| format!("{changed} pixels differ:\n{out}"))
+}
+
+fn assert_snapshot(name: &str, image: Vec<Vec<bool>>) {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/snapshots")
+        .join(format!("{name}.pbm"));
+
+    if env::var_os("UPDATE_SNAPSHOTS").is_some() {
+        fs::write(&path, to_pbm(&image)).unwrap();
+        return;
+    }
+
+    let reference = fs::read_to_string(&path).unwrap_or_else(|err| {
+        panic!(
+            "Failed to read reference {path:?} (run with UPDATE_SNAPSHOTS=1 to create it): {err}"
+        )
+    });
+    if let Some(diff) = diff(&image, &from_pbm(&reference)) {
+        let actual = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join(format!("{name}.pbm"));
+        fs::write(&actual, to_pbm(&image)).unwrap();
+        panic!(
+            "Snapshot {name:?} doesn't match {path:?}, rendered image written to {actual:?}\n{diff}"
+        );
+    }
+}
+
+fn random() -> Random<Seeded> {
+    Random::seed_from_u64(0)
+}
+
+/// Start the first level the same way a player would
+fn first_level(random: &mut Random<Seeded>) -> Context {
+    let mut ctx = Context::new();
+    Button::Center.press(&mut ctx);
+    ctx.tick(random);
+    ctx
+}
+
+#[test]
+fn intro() {
+    let intro = Intro::new();
+    assert_snapshot("intro", render(|frame| intro.render(frame)));
+}
+
+#[test]
+fn intro_settings_hint() {
+    let mut intro = Intro::new();
+    while !intro.showing_hint() {
+        intro.tick();
+    }
+    assert_snapshot("intro-hint", render(|frame| intro.render(frame)));
+}
+
+#[test]
+fn game_with_narrator() {
+    let mut random = random();
+    let mut ctx = first_level(&mut random);
+    for _ in 0..40 {
+        ctx.tick(&mut random);
+    }
+    assert_snapshot("game-narrator", render(|frame| ctx.render(frame)));
+}
+
+#[test]
+fn game_mid_drop() {
+    let mut random = random();
+    let mut ctx = first_level(&mut random);
+    for _ in 0..100 {
+        ctx.tick(&mut random);
+    }
+    // skip narrator
+    Button::Down.press(&mut ctx);
+    for _ in 0..30 {
+        ctx.tick(&mut random);
+    }
+    Button::Left.press(&mut ctx);
+    Button::Up.press(&mut ctx);
+    for _ in 0..40 {
+        ctx.tick(&mut random);
+    }
+    assert_snapshot("game-mid-drop", render(|frame| ctx.render(frame)));
+}
+
+#[test]
+fn game_hold() {
+    let mut random = random();
+    let mut ctx = first_level(&mut random);
+    for _ in 0..100 {
+        ctx.tick(&mut random);
+    }
+    // skip narrator
+    Button::Down.press(&mut ctx);
+    for _ in 0..30 {
+        ctx.tick(&mut random);
+    }
+    // holding center puts the piece on hold
+    Button::Center.press(&mut ctx);
+    for _ in 0..Gestures::DEFAULT.long_press {
+        ctx.tick(&mut random);
+    }
+    ctx.handle(ButtonEvent::new(
+        Button::Center,
+        Kind::LongPress,
+        ctx.ticks(),
+    ));
+    for _ in 0..14 {
+        ctx.tick(&mut random);
+    }
+    Button::Center.release(&mut ctx);
+    assert_snapshot("game-hold", render(|frame| ctx.render(frame)));
+}
+
+#[test]
+fn paused() {
+    let mut random = random();
+    let mut ctx = first_level(&mut random);
+    for _ in 0..100 {
+        ctx.tick(&mut random);
+    }
+    // skip narrator
+    Button::Down.press(&mut ctx);
+    for _ in 0..30 {
+        ctx.tick(&mut random);
+    }
+    // left and right together pause
+    let mut input = Input::new();
+    for held in [[Button::Left, Button::Right].as_slice(), &[]] {
+        input.update(ctx.ticks(), |button| held.contains(&button));
+        ctx.consume(input.queue());
+    }
+    Button::Down.press(&mut ctx);
+    ctx.tick(&mut random);
+    assert_snapshot("paused", render(|frame| ctx.render(frame)));
+}
+
+#[test]
+fn game_next_level() {
+    let mut random = random();
+    // without obstacles the blade falls right through
+    let mut game = Game::new(1);
+    for _ in 0..45 {
+        game.tick(&mut random);
+    }
+    assert!(game.transition().is_none());
+    assert_snapshot("game-next-level", render(|frame| game.render(frame)));
+}
+
+#[test]
+fn gameover() {
+    let score = Score {
+        rows: 120,
+        combo: 45,
+        blade: 73,
+        tough: 25,
+        drop: 38,
+    };
+    let gameover = Gameover::new(3, score);
+    assert_snapshot("gameover", render(|frame| gameover.render(frame)));
+}
+
+#[test]
+fn demo() {
+    let mut random = random();
+    let mut demo = Demo::new();
+    for _ in 0..100 {
+        demo.tick(&mut random);
+    }
+    assert_snapshot("demo", render(|frame| demo.render(frame)));
+}
+
+#[test]
+fn initials() {
+    let score = Score {
+        rows: 120,
+        ..Score::new()
+    };
+    let mut initials = Initials::new(2, score);
+    initials.button_up();
+    initials.button_right();
+    assert_snapshot("initials", render(|frame| initials.render(frame)));
+}
+
+#[test]
+fn highscores() {
+    let mut highscores = HighScores::new();
+    for (initials, score) in [(b"KPC", 1234), (b"ABC", 56), (b"XYZ", 7)] {
+        highscores.insert(Entry {
+            initials: *initials,
+            score,
+        });
+    }
+    assert_snapshot("highscores", render(|frame| highscores.render(frame)));
+}
+
+#[test]
+fn settings() {
+    let mut menu = Menu::new(Settings::new());
+    menu.button_down();
+    menu.button_right();
+    assert_snapshot("settings", render(|frame| menu.render(frame)));
+}
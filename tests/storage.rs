@@ -0,0 +1,106 @@
+use game_chop_chop::storage::{self, Error, MemoryFlash, PowerLoss, Store};
+
+const NAME: u8 = 1;
+const COUNT: u8 = 2;
+
+fn reopen(store: Store<MemoryFlash>) -> Store<MemoryFlash> {
+    let mut flash = store.into_inner();
+    flash.restore_power();
+    Store::open(flash).unwrap()
+}
+
+#[test]
+fn values_survive_reopen() {
+    let mut store = Store::open(MemoryFlash::new()).unwrap();
+    assert_eq!(store.get(NAME), None);
+
+    store.set(NAME, b"abc").unwrap();
+    store.set(COUNT, &[1]).unwrap();
+    store.set(NAME, b"xyz").unwrap();
+    store.commit().unwrap();
+    // not committed
+    store.set(COUNT, &[2]).unwrap();
+
+    let store = reopen(store);
+    assert_eq!(store.get(NAME), Some(&b"xyz"[..]));
+    assert_eq!(store.get(COUNT), Some(&[1][..]));
+}
+
+#[test]
+fn too_much_data() {
+    let mut store = Store::open(MemoryFlash::new()).unwrap();
+    let value = [0; 200];
+    store.set(NAME, &value).unwrap();
+    assert_eq!(store.set(COUNT, &value), Err(Error::Full));
+    assert_eq!(store.get(COUNT), None);
+    assert_eq!(store.get(NAME), Some(&value[..]));
+}
+
+#[test]
+fn power_loss_keeps_previous_record() {
+    // cover interrupted programs and interrupted erases of the next sector
+    for commits in [1, 15, 16, 33] {
+        for bytes in (0..storage::SECTOR_SIZE as usize + 300).step_by(7) {
+            let mut store = Store::open(MemoryFlash::new()).unwrap();
+            for count in 0..commits {
+                store.set(COUNT, &[count]).unwrap();
+                store.commit().unwrap();
+            }
+
+            let mut flash = store.into_inner();
+            flash.cut_power_after(bytes);
+            let mut store = Store::open(flash).unwrap();
+            store.set(COUNT, &[commits]).unwrap();
+            let result = store.commit();
+
+            let mut store = reopen(store);
+            let value = store.get(COUNT).map(|value| value[0]);
+            match result {
+                Ok(()) => assert_eq!(value, Some(commits)),
+                // the new record may be complete before the rest of the page is written
+                Err(Error::Flash(PowerLoss)) => assert!(
+                    value == Some(commits - 1) || value == Some(commits),
+                    "{commits} commits, power lost after {bytes} bytes: {value:?}"
+                ),
+                Err(err) => panic!("unexpected error: {err:?}"),
+            }
+
+            // writing still works after recovering
+            store.set(COUNT, &[100]).unwrap();
+            store.commit().unwrap();
+            assert_eq!(reopen(store).get(COUNT), Some(&[100][..]));
+        }
+    }
+}
+
+#[test]
+fn wear_is_spread_over_all_sectors() {
+    let mut store = Store::open(MemoryFlash::new()).unwrap();
+    for count in 0..1000u32 {
+        store.set(COUNT, &count.to_le_bytes()).unwrap();
+        store.commit().unwrap();
+    }
+    let store = reopen(store);
+    assert_eq!(store.get(COUNT), Some(&999u32.to_le_bytes()[..]));
+
+    let flash = store.into_inner();
+    let erases = (0..storage::SECTORS as usize)
+        .map(|sector| flash.erase_count(sector))
+        .collect::<Vec<_>>();
+    // every sector holds 16 records
+    let min = *erases.iter().min().unwrap();
+    let max = *erases.iter().max().unwrap();
+    assert!(min >= 1000 / 16 / 4, "{erases:?}");
+    assert!(max - min <= 1, "{erases:?}");
+}
+
+#[test]
+fn garbage_is_ignored() {
+    let mut flash = MemoryFlash::new();
+    flash.cut_power_after(0);
+    let mut store = Store::open(flash).unwrap();
+    store.set(NAME, b"abc").unwrap();
+    // a record that was never finished
+    assert_eq!(store.commit(), Err(Error::Flash(PowerLoss)));
+    assert_eq!(reopen(store).get(NAME), None);
+}
@@ -0,0 +1,226 @@
+mod render;
+
+use crate::render::Charset;
+use anyhow::{Context as _, Result, bail};
+use clap::Parser;
+use crossterm::{
+    cursor,
+    event::{
+        self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags,
+        PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+    },
+    execute, queue,
+    style::Print,
+    terminal::{self, ClearType},
+};
+use embedded_graphics::pixelcolor::BinaryColor;
+use embedded_graphics::prelude::*;
+use game_chop_chop::ctx::Context;
+use game_chop_chop::gfx::Framebuffer;
+use game_chop_chop::input::{Button, DropStyle, Input};
+use game_chop_chop::random::Random;
+use game_chop_chop::replay;
+use rand::SeedableRng;
+use std::fs;
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+type Recorder = replay::Recorder<String>;
+
+/// Without key release events, down counts as held until it stopped repeating for this many ticks
+const RELEASE_AFTER: u32 = 10;
+
+#[derive(Debug, Parser)]
+#[command(version, about = "Play the game in the terminal")]
+struct Args {
+    /// Characters used to draw the display
+    #[arg(long, value_enum, default_value = "half-block")]
+    charset: Charset,
+    /// Seed for the piece sequence, random if omitted
+    #[arg(long)]
+    seed: Option<u64>,
+    /// Buttons that drop pieces, modern or classic
+    #[arg(long, default_value = "modern", value_parser = parse_drop_style)]
+    drop: DropStyle,
+    /// Record the session as replay into this file
+    #[arg(long)]
+    record: Option<PathBuf>,
+}
+
+fn parse_drop_style(s: &str) -> Result<DropStyle, String> {
+    s.parse()
+        .map_err(|_| format!("Unknown drop style: {s:?}, expected modern or classic"))
+}
+
+enum Key {
+    Button(Button),
+    /// Left and right at once, the chord that pauses the game
+    Pause,
+    Quit,
+}
+
+fn map_key(key: KeyEvent) -> Option<Key> {
+    let key = match key.code {
+        KeyCode::Up => Key::Button(Button::Up),
+        KeyCode::Down => Key::Button(Button::Down),
+        KeyCode::Left => Key::Button(Button::Left),
+        KeyCode::Right => Key::Button(Button::Right),
+        KeyCode::Char(' ') | KeyCode::Enter => Key::Button(Button::Center),
+        KeyCode::Char('p') => Key::Pause,
+        KeyCode::Char('q') | KeyCode::Esc => Key::Quit,
+        KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => Key::Quit,
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn help(drop_style: DropStyle) -> &'static str {
+    match drop_style {
+        DropStyle::Modern => {
+            "arrows: move/rotate/soft drop, up twice: rotate the other way, space: drop, hold space: hold, p: pause, q: quit"
+        }
+        DropStyle::Classic => {
+            "arrows: move/rotate/drop, up twice: rotate the other way, space: hold, p: pause, q: quit"
+        }
+    }
+}
+
+fn draw<W: Write>(out: &mut W, lines: &[String], help: &str) -> Result<()> {
+    for (y, line) in lines.iter().enumerate() {
+        queue!(out, cursor::MoveTo(0, y as u16), Print(line))?;
+    }
+    // the help changes with the drop style, clear what's left of a longer one
+    queue!(
+        out,
+        cursor::MoveTo(0, lines.len() as u16),
+        Print(help),
+        terminal::Clear(ClearType::UntilNewLine)
+    )?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Play until the player quits, returns the number of ticks played
+///
+/// Keys are turned into button states that are sampled once per tick, the
+/// same way the firmware does. If the terminal doesn't report key releases,
+/// buttons are held for a single tick, except down which is held until the
+/// key stops repeating.
+fn run<W: Write>(
+    out: &mut W,
+    args: &Args,
+    seed: u64,
+    recorder: &mut Recorder,
+    releases: bool,
+) -> Result<u32> {
+    let mut random = Random::seed_from_u64(seed);
+    let mut ctx = Context::new().with_drop_style(args.drop);
+    let mut frame = Framebuffer::new();
+    let mut input = Input::new();
+    // buttons that are held, and the ones pressed during the current tick
+    let mut held = [false; Button::ALL.len()];
+    let mut pressed = [false; Button::ALL.len()];
+    // tick down was last pressed in, only used without release events
+    let mut down_pressed = None;
+
+    let mut next_tick = Instant::now();
+    for tick in 0.. {
+        // process input until the next frame is due
+        loop {
+            let timeout = next_tick.saturating_duration_since(Instant::now());
+            if !event::poll(timeout)? {
+                break;
+            }
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            match (map_key(key), key.kind) {
+                (Some(Key::Button(button)), KeyEventKind::Release) => {
+                    held[button as usize] = false;
+                }
+                (Some(Key::Button(button)), _) => {
+                    pressed[button as usize] = true;
+                    if releases {
+                        held[button as usize] = true;
+                    } else if button == Button::Down {
+                        down_pressed = Some(tick);
+                    }
+                }
+                (Some(Key::Pause), KeyEventKind::Release) => (),
+                (Some(Key::Pause), _) => {
+                    pressed[Button::Left as usize] = true;
+                    pressed[Button::Right as usize] = true;
+                }
+                (Some(Key::Quit), KeyEventKind::Release) => (),
+                (Some(Key::Quit), _) => return Ok(tick),
+                (None, _) => (),
+            }
+        }
+        // same frame rate as the firmware
+        next_tick += Duration::from_millis(ctx.settings().frame_delay.into());
+
+        if !releases {
+            held[Button::Down as usize] =
+                down_pressed.is_some_and(|pressed| tick - pressed < RELEASE_AFTER);
+        }
+        input.update(tick, |button| {
+            held[button as usize] || pressed[button as usize]
+        });
+        pressed = [false; Button::ALL.len()];
+        while let Some(event) = input.pop() {
+            recorder.record(event)?;
+            ctx.handle(event);
+        }
+
+        ctx.tick(&mut random);
+
+        frame.clear(BinaryColor::Off)?;
+        ctx.render(&mut frame);
+        draw(
+            out,
+            &args.charset.lines(&frame),
+            help(ctx.settings().drop_style),
+        )?;
+    }
+    bail!("Played for too long")
+}
+
+fn main() -> Result<()> {
+    let args = Args::parse();
+
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    execute!(
+        stdout,
+        terminal::EnterAlternateScreen,
+        terminal::Clear(ClearType::All),
+        cursor::Hide
+    )?;
+    let releases = terminal::supports_keyboard_enhancement().unwrap_or(false);
+    if releases {
+        execute!(
+            stdout,
+            PushKeyboardEnhancementFlags(KeyboardEnhancementFlags::REPORT_EVENT_TYPES)
+        )?;
+    }
+
+    let seed = args.seed.unwrap_or_else(rand::random);
+    let mut recorder = Recorder::new(String::new(), seed, args.drop)?;
+    let result = run(&mut stdout, &args, seed, &mut recorder, releases);
+
+    if releases {
+        execute!(stdout, PopKeyboardEnhancementFlags)?;
+    }
+    execute!(stdout, cursor::Show, terminal::LeaveAlternateScreen)?;
+    terminal::disable_raw_mode()?;
+    let end = result?;
+
+    if let Some(path) = &args.record {
+        let replay = recorder.finish(end)?;
+        fs::write(path, replay)
+            .with_context(|| format!("Failed to write replay file: {path:?}"))?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use embedded_graphics::prelude::*;
+use game_chop_chop::gfx::{self, Framebuffer};
+
+#[derive(Debug, Clone, Copy, PartialEq, clap::ValueEnum)]
+pub enum Charset {
+    /// Two pixels per character, works with most fonts
+    HalfBlock,
+    /// Eight pixels per character, needs a font with braille support
+    Braille,
+}
+
+impl Charset {
+    /// Number of pixels covered by one character
+    const fn cell(&self) -> (i32, i32) {
+        match self {
+            Charset::HalfBlock => (1, 2),
+            Charset::Braille => (2, 4),
+        }
+    }
+
+    fn char_at(&self, frame: &Framebuffer, origin: Point) -> char {
+        let on = |x, y| gfx::pixel(frame, origin + Point::new(x, y));
+        match self {
+            Charset::HalfBlock => match (on(0, 0), on(0, 1)) {
+                (false, false) => ' ',
+                (true, false) => '▀',
+                (false, true) => '▄',
+                (true, true) => '█',
+            },
+            Charset::Braille => {
+                // bit order of the unicode braille patterns block
+                const DOTS: [(i32, i32); 8] = [
+                    (0, 0),
+                    (0, 1),
+                    (0, 2),
+                    (1, 0),
+                    (1, 1),
+                    (1, 2),
+                    (0, 3),
+                    (1, 3),
+                ];
+                let mut bits = 0;
+                for (idx, (x, y)) in DOTS.into_iter().enumerate() {
+                    if on(x, y) {
+                        bits |= 1 << idx;
+                    }
+                }
+                char::from_u32(0x2800 + bits).unwrap_or(' ')
+            }
+        }
+    }
+
+    /// Convert a frame into lines of text, including a border around the display
+    pub fn lines(&self, frame: &Framebuffer) -> Vec<String> {
+        let (width, height) = self.cell();
+        let columns = (gfx::DISPLAY_WIDTH / width) as usize;
+
+        let mut lines = Vec::new();
+        lines.push(format!("┌{}┐", "─".repeat(columns)));
+        for y in (0..gfx::DISPLAY_HEIGHT).step_by(height as usize) {
+            let mut line = String::from("│");
+            for x in (0..gfx::DISPLAY_WIDTH).step_by(width as usize) {
+                line.push(self.char_at(frame, Point::new(x, y)));
+            }
+            line.push('│');
+            lines.push(line);
+        }
+        lines.push(format!("└{}┘", "─".repeat(columns)));
+        lines
+    }
+}